@@ -0,0 +1,49 @@
+//! Compares `SE3Transform::transform` for identity and non-identity Transforms.
+//!
+//! Run with `cargo bench --bench identity_transform`.
+
+use std::{hint::black_box, time::Instant};
+
+use geometry::{CoordinateSystem, IsTransform, LeftCameraSE3, Point, RightCameraSE3, SE3Transform};
+use nalgebra::{Isometry3, Translation3, UnitQuaternion, Vector3};
+
+const N_POINTS: usize = 1_000_000;
+const N_ROUNDS: usize = 20;
+
+fn main() {
+    let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+    let right_se3_at_0 = CoordinateSystem::<RightCameraSE3, Isometry3<f32>>::at_time(0);
+    let points: Vec<_> = (0..N_POINTS)
+        .map(|i| {
+            let x = i as f32 * 1e-6;
+            Point::new(
+                left_se3_at_0,
+                Isometry3::from_parts(
+                    Translation3::new(x, -x, 1.),
+                    UnitQuaternion::from_scaled_axis(Vector3::new(0.1, x, 0.3)),
+                ),
+            )
+        })
+        .collect();
+
+    let identity = SE3Transform::new(right_se3_at_0, left_se3_at_0, Isometry3::identity());
+    let motion = SE3Transform::new(
+        right_se3_at_0,
+        left_se3_at_0,
+        Isometry3::from_parts(
+            Translation3::new(0.1, 0., 0.),
+            UnitQuaternion::from_scaled_axis(Vector3::new(0.01, 0.02, 0.03)),
+        ),
+    );
+
+    for (name, transform) in [("identity", identity), ("non-identity", motion)] {
+        let start = Instant::now();
+        for _ in 0..N_ROUNDS {
+            for point in points.iter() {
+                black_box(black_box(transform).transform(*point));
+            }
+        }
+        let per_point = start.elapsed() / (N_ROUNDS * N_POINTS) as u32;
+        println!("{:>12}: {:?} per Point", name, per_point);
+    }
+}
@@ -0,0 +1,407 @@
+//! Provides a validated collection of the **static** calibration of a multi-Camera rig:
+//! the intrinsics of each Camera and the extrinsics between them.
+//!
+//! A [`CameraRig`] can be exported with [`CameraRig::to_bytes`] as a single versioned blob for deployment.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::{self, Debug, Display},
+};
+
+use nalgebra::{Isometry3, Matrix3, Quaternion, RealField, Translation3, UnitQuaternion};
+use serde::Serialize;
+
+use crate::{
+    columnar::Reader, id_from_name, IsCoordinateSystemId, StaticProjectiveTransform,
+    StaticSE3Transform,
+};
+
+/// Reasons that a [`CameraRigBuilder`] can fail to build a [`CameraRig`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CameraRigError {
+    /// The intrinsics of the Camera with the given SE3 id are not a valid Camera matrix.
+    InvalidIntrinsics { id: &'static str, reason: String },
+    /// The extrinsics `dst <- src` contradict the extrinsics already added to the rig.
+    InconsistentExtrinsics {
+        dst: &'static str,
+        src: &'static str,
+    },
+}
+
+impl Display for CameraRigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidIntrinsics { id, reason } => {
+                write!(f, "Invalid intrinsics for {}: {}.", id, reason)
+            }
+            Self::InconsistentExtrinsics { dst, src } => write!(
+                f,
+                "Extrinsics from {} to {} are inconsistent with the rest of the rig.",
+                src, dst,
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CameraRigError {}
+
+/// Magic bytes at the start of every blob written by [`CameraRig::to_bytes`].
+const CALIBRATION_MAGIC: [u8; 4] = *b"STRG";
+
+/// Version of the blob format written by [`CameraRig::to_bytes`]. Bump this whenever the layout changes.
+pub const CALIBRATION_VERSION: u32 = 1;
+
+/// Reasons that a blob cannot be loaded by [`CameraRig::from_bytes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CalibrationError {
+    /// The blob does not start with the expected magic bytes, so it is not a calibration at all.
+    BadMagic,
+    /// The blob was written with a format version that this build cannot read.
+    UnsupportedVersion { found: u32, supported: u32 },
+    /// The blob ended before the whole calibration was read.
+    Truncated,
+    /// The blob has bytes left over after the whole calibration was read.
+    TrailingBytes(usize),
+    /// The calibration names an id which is not registered for [`id_from_name`], so it cannot be loaded.
+    UnknownId(String),
+}
+
+impl Display for CalibrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BadMagic => write!(f, "Blob is not a calibration (bad magic bytes)."),
+            Self::UnsupportedVersion { found, supported } => write!(
+                f,
+                "Calibration format version {} is not supported (expected {}).",
+                found, supported,
+            ),
+            Self::Truncated => write!(f, "Blob ended before the calibration was read."),
+            Self::TrailingBytes(n) => write!(f, "Blob has {} trailing bytes.", n),
+            Self::UnknownId(name) => write!(f, "Calibration names unregistered id {}.", name),
+        }
+    }
+}
+
+impl std::error::Error for CalibrationError {}
+
+/// Accumulates the intrinsics and extrinsics of a multi-Camera rig, and validates them in [`CameraRigBuilder::build`].
+#[derive(Debug, Clone)]
+pub struct CameraRigBuilder<T>
+where
+    T: Copy + RealField + Serialize,
+{
+    intrinsics: Vec<(&'static str, &'static str, Matrix3<T>)>,
+    extrinsics: Vec<(&'static str, &'static str, Isometry3<T>)>,
+}
+
+impl<T> Default for CameraRigBuilder<T>
+where
+    T: Copy + RealField + Serialize,
+{
+    fn default() -> Self {
+        Self {
+            intrinsics: Vec::new(),
+            extrinsics: Vec::new(),
+        }
+    }
+}
+
+impl<T> CameraRigBuilder<T>
+where
+    T: Copy + RealField + Serialize,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the intrinsics of a Camera.
+    pub fn intrinsics<ImageId, SE3Id>(
+        mut self,
+        intrinsics: StaticProjectiveTransform<ImageId, SE3Id, T>,
+    ) -> Self
+    where
+        ImageId: IsCoordinateSystemId,
+        SE3Id: IsCoordinateSystemId,
+    {
+        self.intrinsics
+            .push((ImageId::name(), SE3Id::name(), intrinsics.k()));
+        self
+    }
+
+    /// Add the extrinsics between two Cameras.
+    pub fn extrinsics<DstId, SrcId>(
+        mut self,
+        extrinsics: StaticSE3Transform<DstId, SrcId, T>,
+    ) -> Self
+    where
+        DstId: IsCoordinateSystemId,
+        SrcId: IsCoordinateSystemId,
+    {
+        self.extrinsics
+            .push((DstId::name(), SrcId::name(), extrinsics.transform()));
+        self
+    }
+
+    /// Validate the calibration and build the [`CameraRig`].
+    ///
+    /// Each intrinsics matrix must have positive focal lengths and a last row of `[0, 0, 1]`.
+    /// Extrinsics which close a cycle must agree with the existing path through the rig
+    /// to within `tol` (in both translation and rotation angle).
+    pub fn build(self, tol: T) -> Result<CameraRig<T>, CameraRigError> {
+        for (_, id, k) in self.intrinsics.iter() {
+            if k[(2, 0)] != T::zero() || k[(2, 1)] != T::zero() || k[(2, 2)] != T::one() {
+                return Err(CameraRigError::InvalidIntrinsics {
+                    id,
+                    reason: format!(
+                        "last row must be [0, 0, 1], got [{}, {}, {}]",
+                        k[(2, 0)],
+                        k[(2, 1)],
+                        k[(2, 2)],
+                    ),
+                });
+            }
+            if k[(0, 0)] <= T::zero() || k[(1, 1)] <= T::zero() {
+                return Err(CameraRigError::InvalidIntrinsics {
+                    id,
+                    reason: format!(
+                        "focal lengths must be positive, got [{}, {}]",
+                        k[(0, 0)],
+                        k[(1, 1)],
+                    ),
+                });
+            }
+        }
+
+        let mut rig = CameraRig {
+            intrinsics: self.intrinsics,
+            extrinsics: HashMap::new(),
+        };
+        for (dst, src, transform) in self.extrinsics {
+            if let Some(existing) = rig.find_extrinsics(dst, src) {
+                let relative = existing.inverse() * transform;
+                if relative.translation.vector.norm() > tol || relative.rotation.angle() > tol {
+                    return Err(CameraRigError::InconsistentExtrinsics { dst, src });
+                }
+                continue;
+            }
+            rig.extrinsics
+                .entry(src)
+                .or_default()
+                .push((dst, transform));
+            rig.extrinsics
+                .entry(dst)
+                .or_default()
+                .push((src, transform.inverse()));
+        }
+        Ok(rig)
+    }
+}
+
+/// A validated multi-Camera rig, built by a [`CameraRigBuilder`].
+#[derive(Debug, Clone)]
+pub struct CameraRig<T>
+where
+    T: Copy + RealField + Serialize,
+{
+    intrinsics: Vec<(&'static str, &'static str, Matrix3<T>)>,
+    /// For each SE3 id, the neighboring ids and the Transforms to them (`neighbor <- id`).
+    extrinsics: HashMap<&'static str, Vec<(&'static str, Isometry3<T>)>>,
+}
+
+impl<T> CameraRig<T>
+where
+    T: Copy + RealField + Serialize,
+{
+    /// Get the intrinsics of a Camera, if they were added to the rig.
+    pub fn intrinsics<ImageId, SE3Id>(&self) -> Option<StaticProjectiveTransform<ImageId, SE3Id, T>>
+    where
+        ImageId: IsCoordinateSystemId,
+        SE3Id: IsCoordinateSystemId,
+    {
+        self.intrinsics
+            .iter()
+            .find(|(image_id, se3_id, _)| *image_id == ImageId::name() && *se3_id == SE3Id::name())
+            .map(|(_, _, k)| StaticProjectiveTransform::new(*k))
+    }
+
+    /// Get the extrinsics between two Cameras, if they are connected through the rig.
+    pub fn extrinsics<DstId, SrcId>(&self) -> Option<StaticSE3Transform<DstId, SrcId, T>>
+    where
+        DstId: IsCoordinateSystemId,
+        SrcId: IsCoordinateSystemId,
+    {
+        self.find_extrinsics(DstId::name(), SrcId::name())
+            .map(StaticSE3Transform::new)
+    }
+
+    /// Iterate over the extrinsics added to the rig as `(dst, src, dst <- src)`, sorted by `(dst, src)` id names.
+    ///
+    /// Each extrinsics is yielded in both directions. Unlike iterating the underlying map,
+    /// the order is deterministic, e.g. for logging or serialization.
+    pub fn iter_sorted(&self) -> impl Iterator<Item = (&'static str, &'static str, Isometry3<T>)> {
+        let mut edges: Vec<_> = self
+            .extrinsics
+            .iter()
+            .flat_map(|(src, neighbors)| {
+                neighbors
+                    .iter()
+                    .map(move |(dst, dst_from_src)| (*dst, *src, *dst_from_src))
+            })
+            .collect();
+        edges.sort_by_key(|(dst, src, _)| (*dst, *src));
+        edges.into_iter()
+    }
+
+    /// Export the whole calibration as a versioned binary blob, which can be loaded by [`CameraRig::from_bytes`].
+    ///
+    /// The blob holds the magic bytes `STRG` and [`CALIBRATION_VERSION`], then the intrinsics and the
+    /// extrinsics, with ids as strings and numbers as little-endian `f64`. Each extrinsics is written once,
+    /// as `dst <- src` with `dst < src` by id name; the inverse is derived on load.
+    ///
+    /// All ids must be registered for [`id_from_name`], so that the blob can be loaded again; otherwise
+    /// this fails with [`CalibrationError::UnknownId`].
+    pub fn to_bytes(&self) -> Result<Vec<u8>, CalibrationError> {
+        let ids = self
+            .intrinsics
+            .iter()
+            .flat_map(|(image_id, se3_id, _)| [*image_id, *se3_id])
+            .chain(self.extrinsics.keys().copied());
+        for id in ids {
+            if id_from_name(id).is_none() {
+                return Err(CalibrationError::UnknownId(id.to_owned()));
+            }
+        }
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&CALIBRATION_MAGIC);
+        bytes.extend_from_slice(&CALIBRATION_VERSION.to_le_bytes());
+
+        bytes.extend_from_slice(&(self.intrinsics.len() as u32).to_le_bytes());
+        for (image_id, se3_id, k) in self.intrinsics.iter() {
+            write_name(&mut bytes, image_id);
+            write_name(&mut bytes, se3_id);
+            write_values(&mut bytes, k.iter().copied());
+        }
+
+        let extrinsics: Vec<_> = self
+            .iter_sorted()
+            .filter(|(dst, src, _)| dst < src)
+            .collect();
+        bytes.extend_from_slice(&(extrinsics.len() as u32).to_le_bytes());
+        for (dst, src, dst_from_src) in extrinsics {
+            write_name(&mut bytes, dst);
+            write_name(&mut bytes, src);
+            let t = dst_from_src.translation.vector;
+            let q = dst_from_src.rotation.coords;
+            write_values(&mut bytes, [t[0], t[1], t[2], q[0], q[1], q[2], q[3]]);
+        }
+        Ok(bytes)
+    }
+
+    /// Load a calibration written by [`CameraRig::to_bytes`].
+    ///
+    /// Blobs of any other format version are rejected with [`CalibrationError::UnsupportedVersion`],
+    /// rather than being mis-parsed. All ids must be registered for [`id_from_name`].
+    /// The calibration is not re-validated, and rotations are not re-normalized.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CalibrationError> {
+        let mut reader = Reader::new(bytes);
+        if reader.take() != Some(CALIBRATION_MAGIC) {
+            return Err(CalibrationError::BadMagic);
+        }
+        let version = read_u32(&mut reader)?;
+        if version != CALIBRATION_VERSION {
+            return Err(CalibrationError::UnsupportedVersion {
+                found: version,
+                supported: CALIBRATION_VERSION,
+            });
+        }
+
+        let mut intrinsics = Vec::new();
+        for _ in 0..read_u32(&mut reader)? {
+            let image_id = read_name(&mut reader)?;
+            let se3_id = read_name(&mut reader)?;
+            let k = Matrix3::from_column_slice(&read_values::<T, 9>(&mut reader)?);
+            intrinsics.push((image_id, se3_id, k));
+        }
+
+        let mut extrinsics: HashMap<_, Vec<_>> = HashMap::new();
+        for _ in 0..read_u32(&mut reader)? {
+            let dst = read_name(&mut reader)?;
+            let src = read_name(&mut reader)?;
+            let [tx, ty, tz, qx, qy, qz, qw] = read_values::<T, 7>(&mut reader)?;
+            let dst_from_src = Isometry3::from_parts(
+                Translation3::new(tx, ty, tz),
+                UnitQuaternion::new_unchecked(Quaternion::new(qw, qx, qy, qz)),
+            );
+            extrinsics.entry(src).or_default().push((dst, dst_from_src));
+            extrinsics
+                .entry(dst)
+                .or_default()
+                .push((src, dst_from_src.inverse()));
+        }
+
+        if reader.remaining() != 0 {
+            return Err(CalibrationError::TrailingBytes(reader.remaining()));
+        }
+        Ok(Self {
+            intrinsics,
+            extrinsics,
+        })
+    }
+
+    /// Breadth-first search for the Transform `dst <- src`.
+    fn find_extrinsics(&self, dst: &'static str, src: &'static str) -> Option<Isometry3<T>> {
+        let mut visited = HashMap::from([(src, Isometry3::identity())]);
+        let mut queue = VecDeque::from([src]);
+        while let Some(id) = queue.pop_front() {
+            let id_from_src = visited[id];
+            if id == dst {
+                return Some(id_from_src);
+            }
+            for (neighbor, neighbor_from_id) in self.extrinsics.get(id).into_iter().flatten() {
+                if !visited.contains_key(neighbor) {
+                    visited.insert(neighbor, *neighbor_from_id * id_from_src);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        None
+    }
+}
+
+fn write_name(bytes: &mut Vec<u8>, name: &str) {
+    bytes.extend_from_slice(&(name.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(name.as_bytes());
+}
+
+fn write_values<T: Copy + RealField>(bytes: &mut Vec<u8>, values: impl IntoIterator<Item = T>) {
+    for value in values {
+        let value: f64 = nalgebra::try_convert(value).expect("Value must be representable as f64.");
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+}
+
+fn read_u32(reader: &mut Reader) -> Result<u32, CalibrationError> {
+    reader
+        .take()
+        .map(u32::from_le_bytes)
+        .ok_or(CalibrationError::Truncated)
+}
+
+fn read_name(reader: &mut Reader) -> Result<&'static str, CalibrationError> {
+    let len = read_u32(reader)? as usize;
+    let name = reader.take_slice(len).ok_or(CalibrationError::Truncated)?;
+    let name = String::from_utf8_lossy(name);
+    id_from_name(&name).ok_or_else(|| CalibrationError::UnknownId(name.into_owned()))
+}
+
+fn read_values<T: Copy + RealField, const N: usize>(
+    reader: &mut Reader,
+) -> Result<[T; N], CalibrationError> {
+    let mut values = [T::zero(); N];
+    for value in values.iter_mut() {
+        let bytes = reader.take().ok_or(CalibrationError::Truncated)?;
+        *value = nalgebra::convert(f64::from_le_bytes(bytes));
+    }
+    Ok(values)
+}
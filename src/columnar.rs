@@ -0,0 +1,144 @@
+//! Provides a compact columnar encoding for storing many SE3 [`Point`]s at once.
+//!
+//! Serializing Points one-by-one repeats the [`CoordinateSystem`] id for each of them. Instead, the
+//! id is written once in a header, followed by a column of times and a flat `f32` buffer of
+//! `[tx, ty, tz, qx, qy, qz, qw] * N`. All numbers are little-endian.
+
+use std::fmt::{self, Debug, Display};
+
+use nalgebra::{Isometry3, Quaternion, Translation3, UnitQuaternion};
+
+use crate::{CoordinateSystem, IsCoordinateSystemId, IsReprOf, Point};
+
+/// Reasons that a buffer cannot be decoded by [`decode_poses`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColumnarError {
+    /// The buffer ended before all of the Points were read.
+    Truncated,
+    /// The buffer has bytes left over after all of the Points were read.
+    TrailingBytes(usize),
+    /// The buffer holds Points of a different [`IsCoordinateSystemId`] than the one requested.
+    MismatchedId {
+        expected: &'static str,
+        found: String,
+    },
+}
+
+impl Display for ColumnarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "Buffer ended before all Points were read."),
+            Self::TrailingBytes(n) => write!(f, "Buffer has {} trailing bytes.", n),
+            Self::MismatchedId { expected, found } => write!(
+                f,
+                "Buffer holds Points with id {}, but {} was requested.",
+                found, expected,
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ColumnarError {}
+
+/// Encode SE3 [`Point`]s into a columnar buffer, which can be decoded by [`decode_poses`].
+pub fn encode_poses<Id>(points: &[Point<Id, Isometry3<f32>>]) -> Vec<u8>
+where
+    Id: IsCoordinateSystemId,
+{
+    let name = Id::name().as_bytes();
+    let mut bytes = Vec::with_capacity(4 + name.len() + 8 + points.len() * (8 + 7 * 4));
+    bytes.extend_from_slice(&(name.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(name);
+    bytes.extend_from_slice(&(points.len() as u64).to_le_bytes());
+    for point in points {
+        bytes.extend_from_slice(&point.coordinate_system().time().to_le_bytes());
+    }
+    for point in points {
+        let coordinates = point.coordinates();
+        let t = coordinates.translation.vector;
+        let q = coordinates.rotation.coords;
+        for value in [t[0], t[1], t[2], q[0], q[1], q[2], q[3]] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+    bytes
+}
+
+/// Decode SE3 [`Point`]s from a buffer written by [`encode_poses`] for the same `Id`.
+///
+/// Rotations are not re-normalized, so Points round-trip exactly.
+pub fn decode_poses<Id>(bytes: &[u8]) -> Result<Vec<Point<Id, Isometry3<f32>>>, ColumnarError>
+where
+    Id: IsCoordinateSystemId,
+    Isometry3<f32>: IsReprOf<Id::Kind>,
+{
+    let mut reader = Reader::new(bytes);
+    let name_len = u32::from_le_bytes(reader.take().ok_or(ColumnarError::Truncated)?) as usize;
+    let name = reader
+        .take_slice(name_len)
+        .ok_or(ColumnarError::Truncated)?;
+    if name != Id::name().as_bytes() {
+        return Err(ColumnarError::MismatchedId {
+            expected: Id::name(),
+            found: String::from_utf8_lossy(name).into_owned(),
+        });
+    }
+    let n = u64::from_le_bytes(reader.take().ok_or(ColumnarError::Truncated)?) as usize;
+    // Check the length up-front, so that a corrupt `n` cannot cause a huge allocation.
+    let expected_len = n.checked_mul(8 + 7 * 4).ok_or(ColumnarError::Truncated)?;
+    if reader.remaining() < expected_len {
+        return Err(ColumnarError::Truncated);
+    }
+
+    let times = (0..n)
+        .map(|_| reader.take().map(u64::from_le_bytes))
+        .collect::<Option<Vec<_>>>()
+        .ok_or(ColumnarError::Truncated)?;
+    let points = times
+        .into_iter()
+        .map(|time| {
+            let mut values = [0f32; 7];
+            for value in values.iter_mut() {
+                *value = f32::from_le_bytes(reader.take().ok_or(ColumnarError::Truncated)?);
+            }
+            let [tx, ty, tz, qx, qy, qz, qw] = values;
+            Ok(Point::new(
+                CoordinateSystem::at_time(time),
+                Isometry3::from_parts(
+                    Translation3::new(tx, ty, tz),
+                    UnitQuaternion::new_unchecked(Quaternion::new(qw, qx, qy, qz)),
+                ),
+            ))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if reader.remaining() != 0 {
+        return Err(ColumnarError::TrailingBytes(reader.remaining()));
+    }
+    Ok(points)
+}
+
+/// Reads fixed-size chunks from the front of a buffer, returning `None` once it runs out.
+pub(crate) struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self(bytes)
+    }
+
+    pub(crate) fn remaining(&self) -> usize {
+        self.0.len()
+    }
+
+    pub(crate) fn take_slice(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.0.len() < len {
+            return None;
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Some(head)
+    }
+
+    pub(crate) fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        Some(self.take_slice(N)?.try_into().expect("Slice has length N."))
+    }
+}
@@ -1,10 +1,57 @@
 //! The core framework, which provides [`CoordinateSystem`]s and [`Point`]s.
 
-use serde::Serialize;
-use std::{fmt::Debug, hash::Hash, marker::PhantomData};
+use nalgebra::{Isometry3, Point3, RealField, UnitQuaternion, Vector3, Vector6};
+use serde::{de::Error, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
+use std::{cmp::Ordering, fmt::Debug, hash::Hash, marker::PhantomData};
+
+use crate::{debug_assert_src, se3, IsBitHashable, IsReprKind, IsReprOf};
+
+/// Handedness of the axes of a [`CoordinateSystem`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum Handedness {
+    Left,
+    Right,
+}
 
 /// Marker Trait for Coordinate System IDs.
-pub trait IsCoordinateSystemId: Debug + Default + Copy + Eq + Hash + Serialize {}
+pub trait IsCoordinateSystemId: Debug + Default + Copy + Eq + Hash + Serialize {
+    /// Kind of Representation that [`CoordinateSystem`]s with this id may use.
+    type Kind: IsReprKind;
+
+    /// Handedness of the axes of [`CoordinateSystem`]s with this id. Right-handed unless specified.
+    const HANDEDNESS: Handedness = Handedness::Right;
+
+    /// Stable name of the id, used to tag serialized [`CoordinateSystem`]s.
+    ///
+    /// It must not change once data has been saved, so it is not derived from the type (e.g. with
+    /// `std::any::type_name`, whose output may change between compilers). [`crate::define_coordinate_system_id!`]
+    /// uses the identifier of the id.
+    fn name() -> &'static str;
+}
+
+/// Marker Trait for the clock which the `time` of a [`CoordinateSystem`] is measured by.
+///
+/// [`CoordinateSystem`]s with different clocks are different types, so their times cannot be
+/// mixed up; use [`CoordinateSystem::convert_clock`] to move between clocks explicitly:
+///
+/// ```compile_fail
+/// use geometry::{CoordinateSystem, IsClock, LeftCameraSE3};
+/// use nalgebra::Isometry3;
+///
+/// #[derive(Debug, Default, Clone, Copy)]
+/// struct SensorTicks;
+/// impl IsClock for SensorTicks {}
+///
+/// let ros = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+/// let ticks = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>, SensorTicks>::at_time(0);
+/// let _ = ros == ticks;
+/// ```
+pub trait IsClock: Debug + Default + Copy {}
+
+/// The clock used by [`CoordinateSystem`]s unless specified otherwise.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultClock;
+impl IsClock for DefaultClock {}
 
 /// A Coordinate System. [`Point`] coordinates are written relative to a [`CoordinateSystem`].
 /// [`CoordinateSystem`]s are defined by three attributes:
@@ -12,71 +59,471 @@ pub trait IsCoordinateSystemId: Debug + Default + Copy + Eq + Hash + Serialize {
 ///  - a `time`, which is known only at run-time
 ///  - a Representation (`Repr`) (e.g., [`nalgebra::Isometry3`] or [`nalgebra::Vector2`])
 ///
+/// The `time` is measured by a `Clock` (see [`IsClock`]), which is [`DefaultClock`] unless specified.
+///
 /// [`Point`]s written in this [`CoordinateSystem`] have their `coordinates` expressed in its Representation `Repr`.
-#[derive(Debug, Clone, Copy, Serialize)]
-pub struct CoordinateSystem<Id: IsCoordinateSystemId, Repr: Debug + Copy + Serialize> {
+///
+/// A [`CoordinateSystem`] serializes as its `id`'s [`IsCoordinateSystemId::name`] and its `time`.
+/// Deserializing checks the `id` tag, so data cannot be loaded into the wrong [`CoordinateSystem`].
+#[derive(Debug, Clone, Copy)]
+pub struct CoordinateSystem<
+    Id: IsCoordinateSystemId,
+    Repr: Debug + Copy + Serialize,
+    Clock: IsClock = DefaultClock,
+> {
     id: Id,
     time: u64,
     _r: PhantomData<Repr>,
+    _clock: PhantomData<Clock>,
 }
 
-impl<Id: IsCoordinateSystemId, Repr: Debug + Copy + Serialize> PartialEq
-    for CoordinateSystem<Id, Repr>
+impl<Id: IsCoordinateSystemId, Repr: Debug + Copy + Serialize, Clock: IsClock> PartialEq
+    for CoordinateSystem<Id, Repr, Clock>
 {
     fn eq(&self, other: &Self) -> bool {
         self.id == other.id && self.time == other.time
     }
 }
 
-impl<Id: IsCoordinateSystemId, Repr: Debug + Copy + Serialize> Eq for CoordinateSystem<Id, Repr> {}
+impl<Id: IsCoordinateSystemId, Repr: Debug + Copy + Serialize, Clock: IsClock> Eq
+    for CoordinateSystem<Id, Repr, Clock>
+{
+}
 
-impl<Id: IsCoordinateSystemId, Repr: Debug + Copy + Serialize> Hash for CoordinateSystem<Id, Repr> {
+impl<Id: IsCoordinateSystemId, Repr: Debug + Copy + Serialize, Clock: IsClock> Hash
+    for CoordinateSystem<Id, Repr, Clock>
+{
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         (self.id, self.time).hash(state)
     }
 }
 
-impl<Id: IsCoordinateSystemId, Repr: Debug + Copy + Serialize> CoordinateSystem<Id, Repr> {
+impl<Id: IsCoordinateSystemId, Repr: Debug + Copy + Serialize, Clock: IsClock> Serialize
+    for CoordinateSystem<Id, Repr, Clock>
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("CoordinateSystem", 2)?;
+        state.serialize_field("id", Id::name())?;
+        state.serialize_field("time", &self.time)?;
+        state.end()
+    }
+}
+
+impl<'de, Id, Repr, Clock> Deserialize<'de> for CoordinateSystem<Id, Repr, Clock>
+where
+    Id: IsCoordinateSystemId,
+    Repr: Debug + Copy + Serialize + IsReprOf<Id::Kind>,
+    Clock: IsClock,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename = "CoordinateSystem")]
+        struct Tagged {
+            id: String,
+            time: u64,
+        }
+
+        let tagged = Tagged::deserialize(deserializer)?;
+        if tagged.id != Id::name() {
+            return Err(D::Error::custom(format!(
+                "Expected coordinate system id {:?}, got {:?}.",
+                Id::name(),
+                tagged.id,
+            )));
+        }
+        Ok(Self::at_time(tagged.time))
+    }
+}
+
+impl<Id: IsCoordinateSystemId, Repr: Debug + Copy + Serialize, Clock: IsClock>
+    CoordinateSystem<Id, Repr, Clock>
+{
     #[allow(dead_code)]
     fn id(&self) -> Id {
         self.id
     }
 
-    #[allow(dead_code)]
-    fn time(&self) -> u64 {
+    pub(crate) fn time(&self) -> u64 {
         self.time
     }
 
     /// Get the [`CoordinateSystem`] with the defined `Id` at the target time.
-    pub fn at_time(time: u64) -> Self {
+    ///
+    /// The `Repr` must be valid for the `Id`, so that e.g. an Image id cannot be given SE3 coordinates:
+    ///
+    /// ```compile_fail
+    /// use geometry::{CoordinateSystem, LeftCameraImage};
+    /// use nalgebra::Isometry3;
+    ///
+    /// let _ = CoordinateSystem::<LeftCameraImage, Isometry3<f32>>::at_time(0);
+    /// ```
+    pub fn at_time(time: u64) -> Self
+    where
+        Repr: IsReprOf<Id::Kind>,
+    {
         Self {
             id: Id::default(),
             time,
             _r: PhantomData,
+            _clock: PhantomData,
+        }
+    }
+
+    /// Get the [`CoordinateSystem`] with the same `Id` at a different time.
+    pub fn with_time(self, time: u64) -> Self {
+        Self { time, ..self }
+    }
+
+    /// Move the [`CoordinateSystem`] to a different clock, converting its `time` with `f`.
+    pub fn convert_clock<NewClock: IsClock>(
+        self,
+        f: impl FnOnce(u64) -> u64,
+    ) -> CoordinateSystem<Id, Repr, NewClock> {
+        CoordinateSystem {
+            id: self.id,
+            time: f(self.time),
+            _r: PhantomData,
+            _clock: PhantomData,
+        }
+    }
+}
+
+/// Type-erased version of a [`CoordinateSystem`], whose `id` is only known at run-time.
+///
+/// The `id` is the [`IsCoordinateSystemId::name`], and the Representation is dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub struct DynCoordinateSystem {
+    id: &'static str,
+    time: u64,
+}
+
+impl DynCoordinateSystem {
+    /// Build a [`DynCoordinateSystem`] from an id name at run-time, if the id is registered (see [`crate::id_from_name`]).
+    pub fn from_name(name: &str, time: u64) -> Option<Self> {
+        crate::id_from_name(name).map(|id| Self { id, time })
+    }
+
+    pub fn id(&self) -> &'static str {
+        self.id
+    }
+
+    pub fn time(&self) -> u64 {
+        self.time
+    }
+
+    /// Describe which components differ from `other`, e.g. `id differs: LeftCameraSE3 vs RightCameraSE3`
+    /// or `time differs: 0 vs 1`, for mismatch diagnostics. Empty if they are equal.
+    pub fn diff(&self, other: &Self) -> String {
+        let mut differences = Vec::new();
+        if self.id != other.id {
+            differences.push(format!("id differs: {} vs {}", self.id, other.id));
+        }
+        if self.time != other.time {
+            differences.push(format!("time differs: {} vs {}", self.time, other.time));
+        }
+        differences.join(", ")
+    }
+}
+
+impl<Id: IsCoordinateSystemId, Repr: Debug + Copy + Serialize> From<CoordinateSystem<Id, Repr>>
+    for DynCoordinateSystem
+{
+    fn from(coordinate_system: CoordinateSystem<Id, Repr>) -> Self {
+        Self {
+            id: Id::name(),
+            time: coordinate_system.time,
         }
     }
 }
 
 /// A Point, written relative to some [`CoordinateSystem`].
-#[derive(Debug, Clone, Copy, Serialize)]
-pub struct Point<Id: IsCoordinateSystemId, Repr: Debug + Copy + Serialize> {
-    coordinate_system: CoordinateSystem<Id, Repr>,
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "",
+    deserialize = "Repr: Deserialize<'de> + IsReprOf<Id::Kind>"
+))]
+pub struct Point<
+    Id: IsCoordinateSystemId,
+    Repr: Debug + Copy + Serialize,
+    Clock: IsClock = DefaultClock,
+> {
+    coordinate_system: CoordinateSystem<Id, Repr, Clock>,
     coordinates: Repr,
 }
 
-impl<Id: IsCoordinateSystemId, Repr: Debug + Copy + Serialize> Point<Id, Repr> {
-    pub fn new(coordinate_system: CoordinateSystem<Id, Repr>, coordinates: Repr) -> Self {
+/// Exact equality of both the [`CoordinateSystem`] and the bit patterns of the `coordinates`.
+///
+/// This is not a geometric comparison: e.g. two [`nalgebra::Isometry3`]s whose quaternions
+/// differ only in sign, or by a floating-point rounding error, are not equal. Comparing bit patterns
+/// also makes `0.0` and `-0.0` unequal, and `NaN` equal to itself, so that this is a true `Eq`
+/// which agrees with `Hash`.
+impl<Id: IsCoordinateSystemId, Repr: Debug + Copy + Serialize + IsBitHashable, Clock: IsClock>
+    PartialEq for Point<Id, Repr, Clock>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.coordinate_system == other.coordinate_system
+            && self.coordinates.bits().eq(other.coordinates.bits())
+    }
+}
+
+impl<Id: IsCoordinateSystemId, Repr: Debug + Copy + Serialize + IsBitHashable, Clock: IsClock> Eq
+    for Point<Id, Repr, Clock>
+{
+}
+
+impl<Id: IsCoordinateSystemId, Repr: Debug + Copy + Serialize + IsBitHashable, Clock: IsClock> Hash
+    for Point<Id, Repr, Clock>
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.coordinate_system.hash(state);
+        for bits in self.coordinates.bits() {
+            bits.hash(state);
+        }
+    }
+}
+
+/// A total order which agrees with `Eq`, e.g. for keying a `BTreeMap` by [`Point`]: by `time`, then by
+/// the bit patterns of the `coordinates` in order (translation, then quaternion, for an [`nalgebra::Isometry3`]).
+///
+/// As for `Eq`, this is not geometric: bit patterns do not order negative numbers numerically.
+impl<Id: IsCoordinateSystemId, Repr: Debug + Copy + Serialize + IsBitHashable, Clock: IsClock> Ord
+    for Point<Id, Repr, Clock>
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        // The `Id`s are unit types, so `CoordinateSystem`s of the same type differ only in `time`.
+        self.coordinate_system
+            .time
+            .cmp(&other.coordinate_system.time)
+            .then_with(|| self.coordinates.bits().cmp(other.coordinates.bits()))
+    }
+}
+
+impl<Id: IsCoordinateSystemId, Repr: Debug + Copy + Serialize + IsBitHashable, Clock: IsClock>
+    PartialOrd for Point<Id, Repr, Clock>
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<Id: IsCoordinateSystemId, Repr: Debug + Copy + Serialize, Clock: IsClock>
+    Point<Id, Repr, Clock>
+{
+    pub fn new(coordinate_system: CoordinateSystem<Id, Repr, Clock>, coordinates: Repr) -> Self {
         Self {
             coordinate_system,
             coordinates,
         }
     }
 
-    pub fn coordinate_system(&self) -> CoordinateSystem<Id, Repr> {
+    pub fn coordinate_system(&self) -> CoordinateSystem<Id, Repr, Clock> {
         self.coordinate_system
     }
 
     pub fn coordinates(&self) -> Repr {
         self.coordinates
     }
+
+    /// Borrow the `coordinates`, e.g. to avoid copying a large Representation.
+    pub fn coordinates_ref(&self) -> &Repr {
+        &self.coordinates
+    }
+
+    /// Re-tag the [`Point`] with a different time, leaving its `coordinates` unchanged.
+    ///
+    /// No check is performed: the caller is responsible for knowing that the `coordinates`
+    /// are still valid at the new time (e.g. for a static or slowly-moving Point).
+    pub fn with_time(self, time: u64) -> Self {
+        Self::new(self.coordinate_system.with_time(time), self.coordinates)
+    }
+
+    /// Apply `f` to the `coordinates`, keeping the [`Point`] in the same [`CoordinateSystem`].
+    pub fn map_coordinates(self, f: impl FnOnce(Repr) -> Repr) -> Self {
+        Self::new(self.coordinate_system, f(self.coordinates))
+    }
+
+    /// Fallible version of [`Point::map_coordinates`], e.g. for validating the new `coordinates`.
+    pub fn try_map_coordinates<E>(
+        self,
+        f: impl FnOnce(Repr) -> Result<Repr, E>,
+    ) -> Result<Self, E> {
+        Ok(Self::new(self.coordinate_system, f(self.coordinates)?))
+    }
+
+    /// Move the [`Point`] to a different clock, converting its `time` with `f`.
+    pub fn convert_clock<NewClock: IsClock>(
+        self,
+        f: impl FnOnce(u64) -> u64,
+    ) -> Point<Id, Repr, NewClock> {
+        Point::new(self.coordinate_system.convert_clock(f), self.coordinates)
+    }
+}
+
+impl<Id: IsCoordinateSystemId, T: Copy + RealField + Serialize> Point<Id, Isometry3<T>> {
+    /// Position of the [`Point`], i.e. the translation part of its `coordinates`.
+    pub fn position(&self) -> Vector3<T> {
+        self.coordinates.translation.vector
+    }
+
+    /// Squared Euclidean distance between the positions of two [`Point`]s in the same [`CoordinateSystem`].
+    /// As for [`Point::group_mul`], the [`CoordinateSystem`]s are only compared in debug builds.
+    pub fn squared_distance(&self, other: &Self) -> T {
+        debug_assert_src(self.coordinate_system, other.coordinate_system);
+        (self.position() - other.position()).norm_squared()
+    }
+
+    /// Whether two [`Point`]s are in the same [`CoordinateSystem`], with positions within `atol` of each other
+    /// and rotations within `atol` radians of each other, e.g. for `assert!(a.near(&b, 1e-6))` in tests.
+    pub fn near(&self, other: &Self, atol: T) -> bool {
+        self.coordinate_system == other.coordinate_system
+            && (self.position() - other.position()).norm() <= atol
+            && self
+                .coordinates
+                .rotation
+                .angle_to(&other.coordinates.rotation)
+                <= atol
+    }
+
+    /// Re-express the pose of the [`Point`] relative to the pose of `reference`, i.e. `reference⁻¹ * self`,
+    /// e.g. a landmark relative to the current pose of a robot, both written in the same [`CoordinateSystem`].
+    /// As for [`Point::group_mul`], the [`CoordinateSystem`]s are only compared in debug builds.
+    pub fn relative_to(self, reference: Self) -> Self {
+        debug_assert_src(reference.coordinate_system, self.coordinate_system);
+        self.map_coordinates(|coordinates| reference.coordinates.inverse() * coordinates)
+    }
+
+    /// The identity pose in `coordinate_system`, i.e. the neutral element of [`Point::group_mul`].
+    pub fn identity(coordinate_system: CoordinateSystem<Id, Isometry3<T>>) -> Self {
+        Self::new(coordinate_system, Isometry3::identity())
+    }
+
+    /// Left-multiply the pose of the [`Point`] onto the pose of `rhs`, i.e. `self * rhs`.
+    ///
+    /// As for every per-[`Point`] check (see `IsTransform::transform`), the [`CoordinateSystem`]s are
+    /// only compared in debug builds.
+    pub fn group_mul(self, rhs: Self) -> Self {
+        debug_assert_src(self.coordinate_system, rhs.coordinate_system);
+        rhs.map_coordinates(|coordinates| self.coordinates * coordinates)
+    }
+
+    /// Left-multiply the pose of the [`Point`] onto each pose in `rhs`, i.e. [`Point::group_mul`] over a batch,
+    /// with the same debug-only check of each element.
+    pub fn group_mul_batch(self, rhs: &[Self]) -> Vec<Self> {
+        rhs.iter().map(|point| self.group_mul(*point)).collect()
+    }
+
+    /// Blend N poses in the same [`CoordinateSystem`] by their weights, e.g. animation or control targets.
+    ///
+    /// This is the weighted (Karcher) mean: starting from the first pose `m`, it repeatedly steps by the
+    /// weighted mean of `log(m⁻¹ * pose)` in the tangent space at `m` until the step vanishes. Returns `None`
+    /// for no samples and the only pose for one. The weights must have a positive sum.
+    pub fn blend(samples: &[(Self, T)]) -> Option<Self> {
+        let (first, _) = *samples.first()?;
+        if samples.len() == 1 {
+            return Some(first);
+        }
+        let total_weight = samples
+            .iter()
+            .fold(T::zero(), |total, (_, weight)| total + *weight);
+        assert!(
+            total_weight > T::zero(),
+            "Blend weights must have a positive sum, got {}.",
+            total_weight,
+        );
+        let tolerance = T::default_epsilon() * nalgebra::convert(10.);
+        let mut mean = first.coordinates;
+        for _ in 0..32 {
+            let step = samples
+                .iter()
+                .fold(Vector6::zeros(), |step, (pose, weight)| {
+                    debug_assert_src(first.coordinate_system, pose.coordinate_system);
+                    step + se3::log(mean.inverse() * pose.coordinates) * (*weight / total_weight)
+                });
+            mean *= se3::exp(step);
+            if step.norm() <= tolerance {
+                break;
+            }
+        }
+        Some(Self::new(first.coordinate_system, mean))
+    }
+
+    /// Scale the pose of the [`Point`] along its SE(3) geodesic from the identity, i.e. `exp(t * log(self))`.
+    /// `t = 0.5` gives the "half-way" pose, and `t = -1` gives the inverse.
+    pub fn pow(self, t: T) -> Self {
+        self.map_coordinates(|coordinates| se3::exp(se3::log(coordinates) * t))
+    }
+
+    /// Move the pose toward `target` by a tangent-space step of norm at most `max_step`, i.e.
+    /// `self * exp(δ)` with `δ = log(self⁻¹ * target)` clamped to norm `max_step`, e.g. for a follower
+    /// which cannot teleport. Within one step, this reaches `target`.
+    pub fn step_toward(self, target: Self, max_step: T) -> Self {
+        debug_assert_src(self.coordinate_system, target.coordinate_system);
+        assert!(
+            max_step >= T::zero(),
+            "Maximum step must be non-negative, got {}.",
+            max_step,
+        );
+        let delta = se3::log(self.coordinates.inverse() * target.coordinates);
+        let norm = delta.norm();
+        if norm <= max_step {
+            return target;
+        }
+        self.map_coordinates(|coordinates| coordinates * se3::exp(delta * (max_step / norm)))
+    }
+
+    /// Rotate the [`Point`] in place so that its `+Z` axis points at `target`, keeping its position.
+    ///
+    /// The `+Y` axis is taken as close to `up` as possible, after orthogonalizing against the new `+Z` axis
+    /// (see [`UnitQuaternion::face_towards`]). `target` and `up` are written in the [`CoordinateSystem`] of the Point.
+    pub fn aim_at(self, target: Vector3<T>, up: Vector3<T>) -> Self {
+        let direction = target - self.position();
+        assert!(
+            direction.cross(&up) != Vector3::zeros(),
+            "Direction {:?} to the target must not be zero or parallel to up {:?}.",
+            direction,
+            up,
+        );
+        self.map_coordinates(|mut coordinates| {
+            coordinates.rotation = UnitQuaternion::face_towards(&direction, &up);
+            coordinates
+        })
+    }
+}
+
+impl<Id: IsCoordinateSystemId, T: Copy + RealField + Serialize> Point<Id, Vector3<T>> {
+    /// Build a 3-D [`Point`] from a bare [`Vector3`]. The inverse is `Vector3::from(point)`.
+    pub fn from_vector(
+        coordinate_system: CoordinateSystem<Id, Vector3<T>>,
+        vector: Vector3<T>,
+    ) -> Self {
+        Self::new(coordinate_system, vector)
+    }
+
+    /// Build a 3-D [`Point`] from an [`nalgebra::Point3`].
+    pub fn from_point3(
+        coordinate_system: CoordinateSystem<Id, Vector3<T>>,
+        point: Point3<T>,
+    ) -> Self {
+        Self::new(coordinate_system, point.coords)
+    }
+
+    /// Whether two [`Point`]s are in the same [`CoordinateSystem`], with coordinates within `atol` of each other.
+    pub fn near(&self, other: &Self, atol: T) -> bool {
+        self.coordinate_system == other.coordinate_system
+            && (self.coordinates - other.coordinates).norm() <= atol
+    }
+
+    /// Coordinates of the [`Point`] as an [`nalgebra::Point3`], e.g. for other geometry libraries.
+    pub fn to_point3(&self) -> Point3<T> {
+        Point3::from(self.coordinates)
+    }
+}
+
+/// Drop the [`CoordinateSystem`] of a 3-D [`Point`], keeping its `coordinates`.
+impl<Id: IsCoordinateSystemId, T: Copy + RealField + Serialize> From<Point<Id, Vector3<T>>>
+    for Vector3<T>
+{
+    fn from(point: Point<Id, Vector3<T>>) -> Self {
+        point.coordinates
+    }
 }
@@ -1,18 +1,51 @@
 /// Define Coordinate Frame IDs here.
 use serde::Serialize;
 
-use crate::IsCoordinateSystemId;
+use crate::{ImageRepr, IsCoordinateSystemId, SE3Repr};
 
+/// Define a Coordinate Frame ID, along with the kind of Representation (e.g. [`SE3Repr`] or [`ImageRepr`])
+/// that its [`crate::CoordinateSystem`]s use, and optionally its [`crate::Handedness`] (right-handed by default).
 #[macro_export]
 macro_rules! define_coordinate_system_id {
-    ($id:ident) => {
+    ($id:ident, $kind:ty) => {
+        define_coordinate_system_id!($id, $kind, $crate::Handedness::Right);
+    };
+    ($id:ident, $kind:ty, $handedness:expr) => {
         #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
         pub struct $id {}
-        impl IsCoordinateSystemId for $id {}
+        impl IsCoordinateSystemId for $id {
+            type Kind = $kind;
+            const HANDEDNESS: $crate::Handedness = $handedness;
+
+            fn name() -> &'static str {
+                stringify!($id)
+            }
+        }
+    };
+}
+
+/// Define the Coordinate Frame IDs of this file, and register their names for [`id_from_name`].
+macro_rules! define_registered_coordinate_system_ids {
+    ($(($id:ident, $kind:ty)),* $(,)?) => {
+        $(define_coordinate_system_id!($id, $kind);)*
+
+        const REGISTERED_NAMES: &[&str] = &[$(stringify!($id)),*];
     };
 }
 
-define_coordinate_system_id!(LeftCameraSE3);
-define_coordinate_system_id!(LeftCameraImage);
-define_coordinate_system_id!(RightCameraSE3);
-define_coordinate_system_id!(RightCameraImage);
+define_registered_coordinate_system_ids!(
+    (LeftCameraSE3, SE3Repr),
+    (LeftCameraImage, ImageRepr),
+    (RightCameraSE3, SE3Repr),
+    (RightCameraImage, ImageRepr),
+);
+
+/// Look up the [`IsCoordinateSystemId::name`] of an id defined in this file by its string, e.g. from a log or config.
+///
+/// Ids defined elsewhere (e.g. directly with [`define_coordinate_system_id!`]) are not registered.
+pub fn id_from_name(name: &str) -> Option<&'static str> {
+    REGISTERED_NAMES
+        .iter()
+        .find(|registered| **registered == name)
+        .copied()
+}
@@ -0,0 +1,222 @@
+//! Provides type-erased Transforms, whose [`CoordinateSystem`] ids are only checked at run-time.
+//!
+//! These allow storing Transforms between different [`IsCoordinateSystemId`]s in a single collection,
+//! at the cost of the compile-time checks provided by [`SE3Transform`].
+
+use std::{
+    collections::{hash_map::Entry, HashMap, VecDeque},
+    fmt::{self, Debug, Display},
+};
+
+use nalgebra::{Isometry3, RealField};
+use serde::Serialize;
+
+use crate::{
+    CoordinateSystem, DynCoordinateSystem, IsCoordinateSystemId, IsReprOf, IsTransform, Point,
+    SE3Transform,
+};
+
+/// Type-erased version of [`SE3Transform`].
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct DynSE3Transform<T>
+where
+    T: Copy + RealField + Serialize,
+{
+    dst: DynCoordinateSystem,
+    src: DynCoordinateSystem,
+    transform: Isometry3<T>,
+}
+
+impl<DstId, SrcId, T> From<SE3Transform<DstId, SrcId, T>> for DynSE3Transform<T>
+where
+    DstId: IsCoordinateSystemId,
+    SrcId: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    fn from(transform: SE3Transform<DstId, SrcId, T>) -> Self {
+        Self::new(
+            transform.dst().into(),
+            transform.src().into(),
+            transform.isometry(),
+        )
+    }
+}
+
+impl<T> DynSE3Transform<T>
+where
+    T: Copy + RealField + Serialize,
+{
+    pub fn new(
+        dst: DynCoordinateSystem,
+        src: DynCoordinateSystem,
+        transform: Isometry3<T>,
+    ) -> Self {
+        Self {
+            dst,
+            src,
+            transform,
+        }
+    }
+
+    /// [`DynCoordinateSystem`] of the [`Point`] after applying the Transform.
+    pub fn dst(&self) -> DynCoordinateSystem {
+        self.dst
+    }
+
+    /// [`DynCoordinateSystem`] of the [`Point`] before applying the Transform.
+    pub fn src(&self) -> DynCoordinateSystem {
+        self.src
+    }
+
+    pub fn isometry(&self) -> Isometry3<T> {
+        self.transform
+    }
+
+    /// Invert a type-erased Transform.
+    pub fn invert(&self) -> Self {
+        Self::new(self.src, self.dst, self.transform.inverse())
+    }
+
+    /// Compose two [`DynSE3Transform`]s.
+    pub fn compose_with(&self, rhs: Self) -> Self {
+        assert!(
+            self.src == rhs.dst,
+            "Source coordinate system of `self` {:?} does not match Destination coordinate system of `rhs` {:?} ({}).",
+            self.src,
+            rhs.dst,
+            self.src.diff(&rhs.dst),
+        );
+        Self::new(self.dst, rhs.src, self.transform * rhs.transform)
+    }
+
+    /// Apply the Transform to a [`Point`], checking both the `src` and `dst` [`CoordinateSystem`]s at run-time.
+    ///
+    /// Unlike `IsTransform::transform`, these checks run in release builds too: the ids are erased,
+    /// so they stand in for the compile-time id checks of the typed Transforms, which cannot be skipped.
+    pub fn transform<DstId, SrcId>(
+        &self,
+        point: Point<SrcId, Isometry3<T>>,
+    ) -> Point<DstId, Isometry3<T>>
+    where
+        DstId: IsCoordinateSystemId,
+        SrcId: IsCoordinateSystemId,
+        Isometry3<T>: IsReprOf<DstId::Kind>,
+    {
+        assert!(
+            self.src == point.coordinate_system().into(),
+            "Transform source coordinate system {:?} does not match Point coordinate system {:?} ({}).",
+            self.src,
+            point.coordinate_system(),
+            self.src.diff(&point.coordinate_system().into()),
+        );
+        let dst = CoordinateSystem::<DstId, Isometry3<T>>::at_time(self.dst.time());
+        assert!(
+            self.dst == dst.into(),
+            "Transform destination coordinate system {:?} does not match requested coordinate system {:?}.",
+            self.dst,
+            dst,
+        );
+        Point::new(dst, self.transform * point.coordinates())
+    }
+}
+
+/// A cycle of [`DynSE3Transform`]s which does not compose to identity, found by [`validate_static_tree`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InconsistentCycle {
+    /// The [`DynCoordinateSystem`]s around the cycle, in order. The cycle closes from the last back to the first.
+    pub cycle: Vec<DynCoordinateSystem>,
+}
+
+impl Display for InconsistentCycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Transforms around the cycle ")?;
+        for (i, coordinate_system) in self.cycle.iter().chain(self.cycle.first()).enumerate() {
+            if i > 0 {
+                write!(f, " -> ")?;
+            }
+            write!(f, "{}@{}", coordinate_system.id(), coordinate_system.time())?;
+        }
+        write!(f, " do not compose to identity.")
+    }
+}
+
+impl std::error::Error for InconsistentCycle {}
+
+/// Check that a set of static [`DynSE3Transform`]s, e.g. an extrinsics tree loaded from config, is consistent.
+///
+/// The Transforms form a graph between [`DynCoordinateSystem`]s. Each Transform which closes a cycle
+/// (i.e. each independent cycle) must compose to identity around it, to within `tol` in both
+/// translation and rotation angle. Otherwise, the first offending cycle is returned.
+pub fn validate_static_tree<T>(
+    transforms: &[DynSE3Transform<T>],
+    tol: T,
+) -> Result<(), InconsistentCycle>
+where
+    T: Copy + RealField + Serialize,
+{
+    let mut edges: HashMap<DynCoordinateSystem, Vec<usize>> = HashMap::new();
+    for (i, transform) in transforms.iter().enumerate() {
+        edges.entry(transform.src).or_default().push(i);
+        edges.entry(transform.dst).or_default().push(i);
+    }
+
+    // Breadth-first spanning forest. For each visited node, store its parent and `root <- node`.
+    let mut visited: HashMap<DynCoordinateSystem, (Option<DynCoordinateSystem>, Isometry3<T>)> =
+        HashMap::new();
+    let mut is_tree_edge = vec![false; transforms.len()];
+    for root in transforms.iter().flat_map(|t| [t.src, t.dst]) {
+        if visited.contains_key(&root) {
+            continue;
+        }
+        visited.insert(root, (None, Isometry3::identity()));
+        let mut queue = VecDeque::from([root]);
+        while let Some(node) = queue.pop_front() {
+            let root_from_node = visited[&node].1;
+            for &i in &edges[&node] {
+                let edge = transforms[i];
+                let (neighbor, root_from_neighbor) = if edge.src == node {
+                    (edge.dst, root_from_node * edge.transform.inverse())
+                } else {
+                    (edge.src, root_from_node * edge.transform)
+                };
+                if let Entry::Vacant(entry) = visited.entry(neighbor) {
+                    entry.insert((Some(node), root_from_neighbor));
+                    is_tree_edge[i] = true;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+    }
+
+    for (edge, _) in transforms
+        .iter()
+        .zip(is_tree_edge)
+        .filter(|(_, is_tree_edge)| !is_tree_edge)
+    {
+        let relative = visited[&edge.src].1.inverse() * visited[&edge.dst].1 * edge.transform;
+        if relative.translation.vector.norm() > tol || relative.rotation.angle() > tol {
+            let path_to_root = |mut node| {
+                let mut path = vec![node];
+                while let Some(parent) = visited[&node].0 {
+                    path.push(parent);
+                    node = parent;
+                }
+                path
+            };
+            // Walk `src -> ... -> common ancestor -> ... -> dst`; the edge `dst <- src` closes the cycle.
+            let mut src_path = path_to_root(edge.src);
+            let mut dst_path = path_to_root(edge.dst);
+            while src_path.len() > 1
+                && dst_path.len() > 1
+                && src_path[src_path.len() - 2] == dst_path[dst_path.len() - 2]
+            {
+                src_path.pop();
+                dst_path.pop();
+            }
+            dst_path.pop();
+            src_path.extend(dst_path.into_iter().rev());
+            return Err(InconsistentCycle { cycle: src_path });
+        }
+    }
+    Ok(())
+}
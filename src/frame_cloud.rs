@@ -0,0 +1,152 @@
+//! Provides a point cloud which knows its [`CoordinateSystem`], with a spatial hash for neighbor queries.
+
+use std::collections::HashMap;
+
+use nalgebra::{Isometry3, RealField, Vector3};
+use serde::Serialize;
+
+use crate::{
+    debug_assert_src, CoordinateSystem, IsCoordinateSystemId, IsReprOf, Point, StaticSE3Transform,
+};
+
+/// A cloud of 3-D positions in a single SE3 [`CoordinateSystem`].
+///
+/// The positions are bucketed into a grid of cubic cells of side `cell_size`, so that
+/// [`FrameCloud::radius_query`] only visits nearby cells. Queries are typed by the `Id`, so a cloud
+/// cannot be queried with a [`Point`] from a different [`CoordinateSystem`] id.
+#[derive(Debug, Clone)]
+pub struct FrameCloud<Id, T>
+where
+    Id: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    coordinate_system: CoordinateSystem<Id, Isometry3<T>>,
+    positions: Vec<Vector3<T>>,
+    cell_size: T,
+    grid: HashMap<[i64; 3], Vec<usize>>,
+    /// Smallest and largest occupied cell along each axis, or `None` for an empty cloud.
+    bounds: Option<([i64; 3], [i64; 3])>,
+}
+
+impl<Id, T> FrameCloud<Id, T>
+where
+    Id: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    pub fn new(
+        coordinate_system: CoordinateSystem<Id, Isometry3<T>>,
+        positions: Vec<Vector3<T>>,
+        cell_size: T,
+    ) -> Self {
+        assert!(
+            cell_size > T::zero(),
+            "Cell size must be positive, got {}.",
+            cell_size,
+        );
+        let mut grid: HashMap<[i64; 3], Vec<usize>> = HashMap::new();
+        for (i, position) in positions.iter().enumerate() {
+            grid.entry(cell(position, cell_size)).or_default().push(i);
+        }
+        let bounds = grid.keys().fold(None, |bounds, &key| {
+            let (min, max) = bounds.unwrap_or((key, key));
+            Some((
+                [0, 1, 2].map(|i| min[i].min(key[i])),
+                [0, 1, 2].map(|i| max[i].max(key[i])),
+            ))
+        });
+        Self {
+            coordinate_system,
+            positions,
+            cell_size,
+            grid,
+            bounds,
+        }
+    }
+
+    pub fn coordinate_system(&self) -> CoordinateSystem<Id, Isometry3<T>> {
+        self.coordinate_system
+    }
+
+    pub fn positions(&self) -> &[Vector3<T>] {
+        &self.positions
+    }
+
+    /// Move the cloud into the `DstId` [`CoordinateSystem`] at the same time, keeping the same cell size.
+    pub fn transform_to<DstId>(
+        &self,
+        transform: &StaticSE3Transform<DstId, Id, T>,
+    ) -> FrameCloud<DstId, T>
+    where
+        DstId: IsCoordinateSystemId,
+        Isometry3<T>: IsReprOf<DstId::Kind>,
+    {
+        let isometry = transform.transform();
+        FrameCloud::new(
+            CoordinateSystem::at_time(self.coordinate_system.time()),
+            self.positions
+                .iter()
+                .map(|position| isometry.transform_vector(position) + isometry.translation.vector)
+                .collect(),
+            self.cell_size,
+        )
+    }
+
+    /// Indices of the positions within `radius` of the position of `center`, in increasing order.
+    ///
+    /// Only the occupied cells which overlap the query are visited. If there are more of those than
+    /// positions (e.g. for a very large `radius`), the positions are scanned directly instead.
+    pub fn radius_query(&self, center: Point<Id, Isometry3<T>>, radius: T) -> Vec<usize> {
+        debug_assert_src(self.coordinate_system, center.coordinate_system());
+        let center = center.position();
+        assert!(
+            center.iter().all(|x| x.is_finite()) && radius.is_finite() && radius >= T::zero(),
+            "Query center must be finite and radius must be finite and non-negative, got {:?} and {}.",
+            center,
+            radius,
+        );
+        let Some((occupied_min, occupied_max)) = self.bounds else {
+            return Vec::new();
+        };
+        let offset = Vector3::repeat(radius);
+        let min = cell(&(center - offset), self.cell_size);
+        let max = cell(&(center + offset), self.cell_size);
+        let min = [0, 1, 2].map(|i| min[i].max(occupied_min[i]));
+        let max = [0, 1, 2].map(|i| max[i].min(occupied_max[i]));
+
+        let within = |i: &usize| (self.positions[*i] - center).norm_squared() <= radius * radius;
+        let cells = (0..3)
+            .map(|i| (max[i] as i128 - min[i] as i128 + 1).max(0) as u128)
+            .fold(1u128, u128::saturating_mul);
+        if cells > self.positions.len() as u128 {
+            return (0..self.positions.len()).filter(within).collect();
+        }
+        let mut neighbors = Vec::new();
+        for x in min[0]..=max[0] {
+            for y in min[1]..=max[1] {
+                for z in min[2]..=max[2] {
+                    neighbors.extend(
+                        self.grid
+                            .get(&[x, y, z])
+                            .into_iter()
+                            .flatten()
+                            .copied()
+                            .filter(within),
+                    );
+                }
+            }
+        }
+        neighbors.sort_unstable();
+        neighbors
+    }
+}
+
+/// Grid cell containing `position`.
+fn cell<T: Copy + RealField>(position: &Vector3<T>, cell_size: T) -> [i64; 3] {
+    position
+        .map(|x| {
+            let index: f64 =
+                nalgebra::try_convert((x / cell_size).floor()).expect("Position must be finite.");
+            index as i64
+        })
+        .into()
+}
@@ -0,0 +1,119 @@
+//! Provides homogeneous image points, i.e. points of the projective plane RP2.
+
+use nalgebra::{RealField, Vector2, Vector3};
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
+
+use crate::{CoordinateSystem, IsBitHashable, IsBitScalar, IsCoordinateSystemId, IsReprOf, Point};
+
+/// A pixel in homogeneous coordinates `[x, y, w]`, defined up to a nonzero scale.
+///
+/// Unlike a [`Vector2`] pixel, this can represent points at infinity (`w == 0`), e.g. vanishing
+/// points, or the image of a Point under a [`crate::HomographyTransform`] which maps it to infinity.
+///
+/// Equality is up to scale, e.g. `[20, 40, 2] == [10, 20, 1]`: two pixels are equal when the cross product
+/// of their coordinates is exactly zero.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct HomogeneousPixel<T: Copy + RealField> {
+    coords: Vector3<T>,
+}
+
+impl<T: Copy + RealField> HomogeneousPixel<T> {
+    /// Build from homogeneous coordinates `[x, y, w]`, which must not all be zero.
+    pub fn new(coords: Vector3<T>) -> Self {
+        assert!(
+            coords != Vector3::zeros(),
+            "Homogeneous coordinates must not all be zero."
+        );
+        Self { coords }
+    }
+
+    /// Lift a pixel `[x, y]` to `[x, y, 1]`.
+    pub fn from_pixel(pixel: Vector2<T>) -> Self {
+        Self::new(pixel.push(T::one()))
+    }
+
+    pub fn coords(&self) -> Vector3<T> {
+        self.coords
+    }
+
+    /// Whether the point is at infinity, i.e. `w == 0`.
+    pub fn is_at_infinity(&self) -> bool {
+        self.coords[2] == T::zero()
+    }
+
+    /// Normalize to the pixel `[x / w, y / w]`, or `None` if the point is at infinity.
+    pub fn to_pixel(&self) -> Option<Vector2<T>> {
+        (!self.is_at_infinity()).then(|| self.coords.xy() / self.coords[2])
+    }
+}
+
+impl<T: Copy + RealField> PartialEq for HomogeneousPixel<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.coords.cross(&other.coords) == Vector3::zeros()
+    }
+}
+
+/// Deserializing checks, as [`HomogeneousPixel::new`] does, that the coordinates are not all zero.
+impl<'de, T> Deserialize<'de> for HomogeneousPixel<T>
+where
+    T: Copy + RealField + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename = "HomogeneousPixel")]
+        struct Unchecked<T: Copy + RealField> {
+            coords: Vector3<T>,
+        }
+
+        let unchecked = Unchecked::<T>::deserialize(deserializer)?;
+        if unchecked.coords == Vector3::zeros() {
+            return Err(D::Error::custom(
+                "Homogeneous coordinates must not all be zero.",
+            ));
+        }
+        Ok(Self::new(unchecked.coords))
+    }
+}
+
+/// Hashes the coordinates scaled so that the last nonzero one is `1`, so that scaled copies of a pixel
+/// agree as [`Point`]s too (as long as the scaling is exact in floating-point).
+impl<T: Copy + RealField + IsBitScalar> IsBitHashable for HomogeneousPixel<T> {
+    fn bits(&self) -> impl Iterator<Item = u64> + '_ {
+        let scale = self
+            .coords
+            .iter()
+            .rev()
+            .copied()
+            .find(|x| *x != T::zero())
+            .unwrap_or_else(T::one);
+        let normalized: [T; 3] = (self.coords / scale).into();
+        normalized.into_iter().map(|x| x.to_bits_u64())
+    }
+}
+
+impl<Id: IsCoordinateSystemId, T: Copy + RealField + Serialize> Point<Id, HomogeneousPixel<T>> {
+    /// Normalize to a pixel [`Point`] in the same [`CoordinateSystem`], or `None` if the point
+    /// is at infinity.
+    pub fn to_pixel(&self) -> Option<Point<Id, Vector2<T>>>
+    where
+        Vector2<T>: IsReprOf<Id::Kind>,
+    {
+        let coordinate_system = self.coordinate_system();
+        self.coordinates()
+            .to_pixel()
+            .map(|pixel| Point::new(CoordinateSystem::at_time(coordinate_system.time()), pixel))
+    }
+}
+
+impl<Id: IsCoordinateSystemId, T: Copy + RealField + Serialize> Point<Id, Vector2<T>> {
+    /// Lift a pixel [`Point`] to homogeneous coordinates `[x, y, 1]`, in the same [`CoordinateSystem`].
+    pub fn to_homogeneous(&self) -> Point<Id, HomogeneousPixel<T>>
+    where
+        HomogeneousPixel<T>: IsReprOf<Id::Kind>,
+    {
+        Point::new(
+            CoordinateSystem::at_time(self.coordinate_system().time()),
+            HomogeneousPixel::from_pixel(self.coordinates()),
+        )
+    }
+}
@@ -0,0 +1,136 @@
+//! Provides estimation of [`HomographyTransform`]s from point correspondences.
+
+use std::fmt::{self, Debug, Display};
+
+use nalgebra::{DMatrix, Matrix2, Matrix3, RealField, Vector2};
+use serde::Serialize;
+
+use crate::{CoordinateSystem, HomographyTransform, IsCoordinateSystemId};
+
+/// Reasons that a homography cannot be estimated from a set of correspondences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HomographyError {
+    /// The `dst` and `src` point slices have different lengths.
+    MismatchedLengths { dst: usize, src: usize },
+    /// At least 4 correspondences are needed, but only this many were given.
+    TooFewPoints(usize),
+    /// The correspondences do not determine a unique homography (e.g., the points are collinear).
+    Degenerate,
+}
+
+impl Display for HomographyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MismatchedLengths { dst, src } => write!(
+                f,
+                "Got {} destination points but {} source points.",
+                dst, src,
+            ),
+            Self::TooFewPoints(n) => write!(f, "Need at least 4 correspondences, got {}.", n),
+            Self::Degenerate => write!(f, "Correspondences are degenerate (e.g., collinear)."),
+        }
+    }
+}
+
+impl std::error::Error for HomographyError {}
+
+/// Estimate the homography mapping each of `src_points` to the corresponding `dst_points`,
+/// using the Direct Linear Transform (DLT) with Hartley normalization.
+///
+/// With more than 4 correspondences, this is the algebraic least-squares solution.
+pub fn homography_from_points<DstId, SrcId, T>(
+    dst: CoordinateSystem<DstId, Vector2<T>>,
+    src: CoordinateSystem<SrcId, Vector2<T>>,
+    dst_points: &[Vector2<T>],
+    src_points: &[Vector2<T>],
+) -> Result<HomographyTransform<DstId, SrcId, T>, HomographyError>
+where
+    DstId: IsCoordinateSystemId,
+    SrcId: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    if dst_points.len() != src_points.len() {
+        return Err(HomographyError::MismatchedLengths {
+            dst: dst_points.len(),
+            src: src_points.len(),
+        });
+    }
+    let n = src_points.len();
+    if n < 4 {
+        return Err(HomographyError::TooFewPoints(n));
+    }
+
+    let dst_normalization = hartley_normalization(dst_points).ok_or(HomographyError::Degenerate)?;
+    let src_normalization = hartley_normalization(src_points).ok_or(HomographyError::Degenerate)?;
+
+    // Each correspondence contributes two rows. Pad to at least 9 rows so that the SVD
+    // yields the full right-singular basis, including the null vector.
+    let mut a = DMatrix::<T>::zeros((2 * n).max(9), 9);
+    for (i, (dst_point, src_point)) in dst_points.iter().zip(src_points).enumerate() {
+        let p = src_normalization * src_point.push(T::one());
+        let q = dst_normalization * dst_point.push(T::one());
+        for j in 0..3 {
+            a[(2 * i, 3 + j)] = -q[2] * p[j];
+            a[(2 * i, 6 + j)] = q[1] * p[j];
+            a[(2 * i + 1, j)] = q[2] * p[j];
+            a[(2 * i + 1, 6 + j)] = -q[0] * p[j];
+        }
+    }
+
+    let svd = a.svd(false, true);
+    let v_t = svd.v_t.expect("SVD was asked to compute V^T.");
+    let mut order: Vec<usize> = (0..svd.singular_values.len()).collect();
+    order.sort_by(|&i, &j| {
+        svd.singular_values[i]
+            .partial_cmp(&svd.singular_values[j])
+            .expect("Singular values must not be NaN.")
+    });
+    // A unique solution has a one-dimensional null space.
+    let largest = svd.singular_values[order[8]];
+    if svd.singular_values[order[1]] <= largest * T::default_epsilon().sqrt() {
+        return Err(HomographyError::Degenerate);
+    }
+
+    let h = v_t.row(order[0]);
+    #[rustfmt::skip]
+    let normalized_h = Matrix3::new(
+        h[0], h[1], h[2],
+        h[3], h[4], h[5],
+        h[6], h[7], h[8],
+    );
+    let dst_denormalization = dst_normalization
+        .try_inverse()
+        .expect("Hartley normalization is invertible.");
+    let mut h = dst_denormalization * normalized_h * src_normalization;
+    if h[(2, 2)] != T::zero() {
+        h /= h[(2, 2)];
+    }
+    Ok(HomographyTransform::new(dst, src, h))
+}
+
+/// Similarity Transform that moves the centroid of `points` to the origin and scales them to
+/// have mean distance `sqrt(2)` from it. Returns `None` if the points are collinear.
+fn hartley_normalization<T: Copy + RealField>(points: &[Vector2<T>]) -> Option<Matrix3<T>> {
+    let n: T = nalgebra::convert(points.len() as f64);
+    let centroid = points.iter().fold(Vector2::zeros(), |acc, p| acc + p) / n;
+    let covariance = points.iter().fold(Matrix2::zeros(), |acc, p| {
+        acc + (p - centroid) * (p - centroid).transpose()
+    }) / n;
+    let eigenvalues = covariance.symmetric_eigenvalues();
+    if eigenvalues.min() <= eigenvalues.max() * T::default_epsilon().sqrt() {
+        return None;
+    }
+
+    let mean_distance = points
+        .iter()
+        .fold(T::zero(), |acc, p| acc + (p - centroid).norm())
+        / n;
+    let scale = T::sqrt(nalgebra::convert(2.)) / mean_distance;
+    #[rustfmt::skip]
+    let normalization = Matrix3::new(
+        scale, T::zero(), -scale * centroid[0],
+        T::zero(), scale, -scale * centroid[1],
+        T::zero(), T::zero(), T::one(),
+    );
+    Some(normalization)
+}
@@ -0,0 +1,50 @@
+//! Provides sampling of images at the sub-pixel locations produced by [`crate::ProjectiveTransform`]s.
+
+use nalgebra::{DMatrix, RealField, Scalar, Vector2};
+
+/// Read access to a 2-D grid of pixel values, indexed by `(row, col)`, i.e. `(y, x)`.
+pub trait Index2D<T> {
+    fn width(&self) -> usize;
+    fn height(&self) -> usize;
+    fn get(&self, row: usize, col: usize) -> T;
+}
+
+impl<T: Scalar + Copy> Index2D<T> for DMatrix<T> {
+    fn width(&self) -> usize {
+        self.ncols()
+    }
+
+    fn height(&self) -> usize {
+        self.nrows()
+    }
+
+    fn get(&self, row: usize, col: usize) -> T {
+        self[(row, col)]
+    }
+}
+
+/// Bilinearly interpolate `image` at the sub-pixel location `pixel` (`[x, y]`, with pixel centers
+/// at integer coordinates). Returns `None` if `pixel` is outside of the image.
+pub fn bilinear_sample<T, I>(image: &I, pixel: Vector2<T>) -> Option<T>
+where
+    T: Copy + RealField,
+    I: Index2D<T>,
+{
+    let (x, y) = (pixel[0], pixel[1]);
+    let max_x: T = nalgebra::convert(image.width().checked_sub(1)? as f64);
+    let max_y: T = nalgebra::convert(image.height().checked_sub(1)? as f64);
+    if !(x >= T::zero() && x <= max_x && y >= T::zero() && y <= max_y) {
+        return None;
+    }
+
+    let (x0, y0) = (x.floor(), y.floor());
+    let (dx, dy) = (x - x0, y - y0);
+    let col0: usize = nalgebra::try_convert::<T, f64>(x0)? as usize;
+    let row0: usize = nalgebra::try_convert::<T, f64>(y0)? as usize;
+    let col1 = (col0 + 1).min(image.width() - 1);
+    let row1 = (row0 + 1).min(image.height() - 1);
+
+    let top = image.get(row0, col0) * (T::one() - dx) + image.get(row0, col1) * dx;
+    let bottom = image.get(row1, col0) * (T::one() - dx) + image.get(row1, col1) * dx;
+    Some(top * (T::one() - dy) + bottom * dy)
+}
@@ -0,0 +1,93 @@
+//! Provides an inverse-depth parameterization of [`Point`]s, which stays well-conditioned
+//! for Points that are far away from the Camera that observed them.
+
+use std::fmt::Debug;
+
+use nalgebra::{Isometry3, RealField, Translation3, UnitQuaternion, Vector3};
+use serde::Serialize;
+
+use crate::{CoordinateSystem, IsCoordinateSystemId, Point};
+
+/// A Point parameterized by its bearing and inverse depth from an "anchor" SE3 [`CoordinateSystem`].
+///
+/// The bearing is given by an `azimuth` (about the y-axis, from the z-axis towards the x-axis)
+/// and an `elevation` (towards the negative y-axis). The Euclidean coordinates of the Point in the
+/// `anchor` [`CoordinateSystem`] are `bearing / inverse_depth`, so `inverse_depth == 0` is a Point at infinity.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct InverseDepthPoint<Id, T>
+where
+    Id: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    anchor: CoordinateSystem<Id, Isometry3<T>>,
+    azimuth: T,
+    elevation: T,
+    inverse_depth: T,
+}
+
+impl<Id, T> InverseDepthPoint<Id, T>
+where
+    Id: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    pub fn new(
+        anchor: CoordinateSystem<Id, Isometry3<T>>,
+        azimuth: T,
+        elevation: T,
+        inverse_depth: T,
+    ) -> Self {
+        assert!(
+            inverse_depth >= T::zero(),
+            "Inverse depth must be non-negative, got {}.",
+            inverse_depth,
+        );
+        Self {
+            anchor,
+            azimuth,
+            elevation,
+            inverse_depth,
+        }
+    }
+
+    pub fn anchor(&self) -> CoordinateSystem<Id, Isometry3<T>> {
+        self.anchor
+    }
+
+    pub fn azimuth(&self) -> T {
+        self.azimuth
+    }
+
+    pub fn elevation(&self) -> T {
+        self.elevation
+    }
+
+    pub fn inverse_depth(&self) -> T {
+        self.inverse_depth
+    }
+
+    /// Unit-norm direction from the origin of the `anchor` [`CoordinateSystem`] towards the Point.
+    pub fn bearing(&self) -> Vector3<T> {
+        Vector3::new(
+            self.elevation.cos() * self.azimuth.sin(),
+            -self.elevation.sin(),
+            self.elevation.cos() * self.azimuth.cos(),
+        )
+    }
+
+    /// Convert to a Euclidean [`Point`] in the `anchor` [`CoordinateSystem`].
+    ///
+    /// Panics for Points at infinity (`inverse_depth == 0`).
+    pub fn to_point(&self) -> Point<Id, Isometry3<T>> {
+        assert!(
+            self.inverse_depth > T::zero(),
+            "Cannot convert a Point at infinity (inverse depth 0) to Euclidean coordinates.",
+        );
+        Point::new(
+            self.anchor,
+            Isometry3::from_parts(
+                Translation3::from(self.bearing() / self.inverse_depth),
+                UnitQuaternion::identity(),
+            ),
+        )
+    }
+}
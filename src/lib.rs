@@ -1,17 +1,50 @@
+mod camera_rig;
+mod columnar;
 mod coordinate_system;
 mod coordinate_system_ids;
+mod dyn_transform;
+mod frame_cloud;
+mod homogeneous;
+mod homography;
+mod image;
+mod inverse_depth;
+mod lie;
+mod line;
+mod repr;
+mod ros;
+pub mod serde_isometry;
 mod static_transform;
+#[cfg(any(test, feature = "test-utils"))]
+mod test_utils;
 mod transform;
 
+pub use camera_rig::*;
+pub use columnar::*;
 pub use coordinate_system::*;
 pub use coordinate_system_ids::*;
+pub use dyn_transform::*;
+pub use frame_cloud::*;
+pub use homogeneous::*;
+pub use homography::*;
+pub use image::*;
+pub use inverse_depth::*;
+pub use lie::*;
+pub use line::*;
+pub use repr::*;
+pub use ros::*;
 pub use static_transform::*;
+#[cfg(any(test, feature = "test-utils"))]
+pub use test_utils::*;
 pub use transform::*;
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use nalgebra::{Isometry3, Matrix3, Translation3, UnitQuaternion, Vector2, Vector3};
+    use nalgebra::{
+        DMatrix, Isometry3, Matrix3, SMatrix, SVector, Translation3, UnitQuaternion, Vector2,
+        Vector3,
+    };
+    use serde::Serialize;
 
     const BASELINE: f32 = 0.1;
     const LEFT_FOCAL_LEN: f32 = 100.;
@@ -20,6 +53,65 @@ mod test {
 
     const ATOL: f32 = 1e-6;
 
+    /// Message of a panic caught by [`std::panic::catch_unwind`].
+    fn panic_message(panic: &Box<dyn std::any::Any + Send>) -> &str {
+        panic
+            .downcast_ref::<String>()
+            .map(String::as_str)
+            .or_else(|| panic.downcast_ref::<&str>().copied())
+            .unwrap_or_default()
+    }
+
+    /// Pinhole intrinsics of the LeftCamera, with principal point `(cx, cy)`.
+    fn left_intrinsics(
+        cx: f32,
+        cy: f32,
+    ) -> StaticProjectiveTransform<LeftCameraImage, LeftCameraSE3, f32> {
+        #[rustfmt::skip]
+        let k = Matrix3::new(
+            LEFT_FOCAL_LEN, 0., cx,
+            0., LEFT_FOCAL_LEN, cy,
+            0., 0., 1.,
+        );
+        StaticProjectiveTransform::new(k)
+    }
+
+    /// Pinhole intrinsics of the RightCamera, with its principal point at the origin.
+    fn right_intrinsics() -> StaticProjectiveTransform<RightCameraImage, RightCameraSE3, f32> {
+        #[rustfmt::skip]
+        let k = Matrix3::new(
+            RIGHT_FOCAL_LEN, 0., 0.,
+            0., RIGHT_FOCAL_LEN, 0.,
+            0., 0., 1.,
+        );
+        StaticProjectiveTransform::new(k)
+    }
+
+    /// Left-Right extrinsics of the Stereo rig: the RightCamera sits `BASELINE` along `+X` of the LeftCamera.
+    fn se3_left_from_right(
+        rotation: UnitQuaternion<f32>,
+    ) -> StaticSE3Transform<LeftCameraSE3, RightCameraSE3, f32> {
+        StaticSE3Transform::new(Isometry3::from_parts(
+            Translation3::new(BASELINE, 0., 0.),
+            rotation,
+        ))
+    }
+
+    /// A generic pose, with both translation and rotation about every axis.
+    fn sample_pose() -> Isometry3<f32> {
+        Isometry3::from_parts(
+            Translation3::new(1., 2., 3.),
+            UnitQuaternion::from_euler_angles(0.3, -0.2, 0.1),
+        )
+    }
+
+    /// Pose of the `src` of `transform` in its `dst`, so Transforms can be compared with [`assert_pose_near`].
+    fn as_pose<DstId: IsCoordinateSystemId, SrcId: IsCoordinateSystemId>(
+        transform: SE3Transform<DstId, SrcId, f32>,
+    ) -> Point<DstId, Isometry3<f32>> {
+        Point::new(transform.dst(), transform.isometry())
+    }
+
     /// In this scenario, we have a Stereo pair of Cameras, which are attached
     /// to a rig so that the relative pose between the LeftCamera and the RightCamera is fixed.
     ///
@@ -30,26 +122,10 @@ mod test {
     fn test_stereo() {
         // The following are "Static" Transforms that do not change over time:
         // Set Left-Right Extrinsics.
-        let se3_left_from_right =
-            StaticSE3Transform::<LeftCameraSE3, RightCameraSE3, _>::new(Isometry3::from_parts(
-                Translation3::new(BASELINE, 0., 0.),
-                UnitQuaternion::default(),
-            ));
+        let se3_left_from_right = se3_left_from_right(UnitQuaternion::identity());
         // Set Camera Intrinsics.
-        #[rustfmt::skip]
-        let left_intrinsics =
-            StaticProjectiveTransform::<LeftCameraImage, LeftCameraSE3, _>::new(Matrix3::new(
-                LEFT_FOCAL_LEN, 0f32, 0f32,
-                0f32, LEFT_FOCAL_LEN, 0f32,
-                0f32, 0f32, 1f32,
-            ));
-        #[rustfmt::skip]
-        let right_intrinsics =
-            StaticProjectiveTransform::<RightCameraImage, RightCameraSE3, _>::new(Matrix3::new(
-                RIGHT_FOCAL_LEN, 0f32, 0f32,
-                0f32, RIGHT_FOCAL_LEN, 0f32,
-                0f32, 0f32, 1f32,
-            ));
+        let left_intrinsics = left_intrinsics(0., 0.);
+        let right_intrinsics = right_intrinsics();
 
         // These are our Coordinate Systems at Time = 0.
         //
@@ -142,24 +218,2033 @@ mod test {
             .transform(se3_left_1_from_left_0.transform(point_in_left_se3_at_0));
         assert!(alt_point_in_right_se3_at_1.coordinate_system() == right_se3_at_1);
 
-        assert!(
-            (point_in_right_se3_at_1.coordinates().to_homogeneous()
-                - alt_point_in_right_se3_at_1.coordinates().to_homogeneous())
-            .norm()
-                < ATOL
-        );
+        assert!(point_in_right_se3_at_1.near(&alt_point_in_right_se3_at_1, ATOL));
 
         // NOTE THAT ATTEMPTING:
         // ```
         // se3_right_1_from_right_0.transform(point_in_right_se3_at_1)
         // ```
-        // WILL PANIC (AT RUNTIME)!
+        // WILL PANIC (AT RUNTIME, IN DEBUG BUILDS)!
         // It cannot be caught at compile-time, because the times of CoordinateSystems
         // are not enumerable. But, it will `panic!` at run-time, because the times
-        // do not match up.
+        // do not match up. `try_transform` always performs the check, even in release builds.
         let panic = std::panic::catch_unwind(|| {
             se3_right_1_from_right_0.transform(point_in_right_se3_at_1)
         });
+        assert_eq!(panic.is_err(), cfg!(debug_assertions));
+        // The message names the component which differs.
+        if let Err(panic) = panic {
+            assert!(panic_message(&panic).contains("(time differs: 0 vs 1)"));
+        }
+        assert_eq!(
+            se3_right_1_from_right_0.try_transform(point_in_right_se3_at_1),
+            Err(CoordinateSystemMismatch {
+                expected: right_se3_at_0.into(),
+                found: right_se3_at_1.into(),
+            })
+        );
+        assert!(se3_right_1_from_right_0
+            .try_transform(point_in_right_se3_at_0)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_map_coordinates() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        let point = Point::new(
+            left_se3_at_0,
+            Isometry3::from_parts(
+                Translation3::new(0., 0., POINT_DISTANCE),
+                UnitQuaternion::default(),
+            ),
+        );
+
+        // Add a constant bias to the translation; the CoordinateSystem is unchanged.
+        let biased = point.map_coordinates(|mut coordinates| {
+            coordinates.translation.vector += Vector3::new(BASELINE, 0., 0.);
+            coordinates
+        });
+        assert!(biased.coordinate_system() == left_se3_at_0);
+        assert!(
+            (biased.coordinates().translation.vector - Vector3::new(BASELINE, 0., POINT_DISTANCE))
+                .norm()
+                < ATOL
+        );
+
+        let rejected = point.try_map_coordinates(|coordinates| {
+            if coordinates.translation.vector[2] > 0. {
+                Err("Point is in front of the Camera.")
+            } else {
+                Ok(coordinates)
+            }
+        });
+        assert!(rejected.is_err());
+    }
+
+    #[test]
+    fn test_project_inverse_depth() {
+        let se3_right_from_left = se3_left_from_right(UnitQuaternion::identity()).invert();
+        let right_intrinsics = right_intrinsics();
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+
+        // A Point at a finite depth projects to the same pixel as its Euclidean coordinates.
+        let near = InverseDepthPoint::new(left_se3_at_0, 0.1, 0.05, 1. / POINT_DISTANCE);
+        let near_pixel = right_intrinsics
+            .at_time(0)
+            .project_inverse_depth(near, se3_right_from_left.at_time(0));
+        let expected_near_pixel = right_intrinsics
+            .at_time(0)
+            .transform(se3_right_from_left.at_time(0).transform(near.to_point()));
+        assert!((near_pixel.coordinates() - expected_near_pixel.coordinates()).norm() < 1e-4);
+
+        // A Point at infinity still projects to finite pixels, independent of the baseline.
+        let far = InverseDepthPoint::new(left_se3_at_0, 0.1, 0.05, 0.);
+        let far_pixel = right_intrinsics
+            .at_time(0)
+            .project_inverse_depth(far, se3_right_from_left.at_time(0));
+        assert!(far_pixel.coordinates().iter().all(|c| c.is_finite()));
+        let bearing = far.bearing();
+        assert!(
+            (far_pixel.coordinates()
+                - Vector2::new(bearing[0], bearing[1]) * RIGHT_FOCAL_LEN / bearing[2])
+                .norm()
+                < 1e-4
+        );
+    }
+
+    #[test]
+    fn test_compose_all() {
+        let left_se3 = |time| CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(time);
+        let motion = |dst, src, t: Vector3<f32>| {
+            SE3Transform::new(
+                left_se3(dst),
+                left_se3(src),
+                Isometry3::from_parts(Translation3::from(t), UnitQuaternion::from_scaled_axis(t)),
+            )
+        };
+        let se3_left_3_from_left_2 = motion(3, 2, Vector3::new(0.1, 0.2, 0.3));
+        let se3_left_2_from_left_1 = motion(2, 1, Vector3::new(-0.3, 0.1, 0.2));
+        let se3_left_1_from_left_0 = motion(1, 0, Vector3::new(0.2, -0.1, 0.1));
+
+        let composed = compose_all(&[
+            se3_left_3_from_left_2,
+            se3_left_2_from_left_1,
+            se3_left_1_from_left_0,
+        ])
+        .unwrap();
+        let manual = se3_left_3_from_left_2
+            .compose_with(se3_left_2_from_left_1)
+            .compose_with(se3_left_1_from_left_0);
+        assert!(composed.dst() == left_se3(3));
+        assert!(composed.src() == left_se3(0));
+
+        let point = Point::new(left_se3(0), Isometry3::identity());
+        assert_pose_near(composed.transform(point), manual.transform(point), ATOL);
+
+        assert!(compose_all::<LeftCameraSE3, f32>(&[]).is_none());
+        let panic = std::panic::catch_unwind(|| {
+            compose_all(&[se3_left_3_from_left_2, se3_left_1_from_left_0])
+        });
+        assert!(panic_message(&panic.unwrap_err()).contains("(time differs: 2 vs 1)"));
+    }
+
+    #[test]
+    fn test_dyn_se3_transform() {
+        let se3_left_from_right = se3_left_from_right(UnitQuaternion::identity());
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        let left_se3_at_1 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(1);
+        let right_se3_at_0 = CoordinateSystem::<RightCameraSE3, Isometry3<f32>>::at_time(0);
+        let se3_left_1_from_left_0 = SE3Transform::new(
+            left_se3_at_1,
+            left_se3_at_0,
+            Isometry3::from_parts(
+                Translation3::new(0.1, 0.2, 0.3),
+                UnitQuaternion::from_scaled_axis(Vector3::new(0.1, 0.2, 0.3)),
+            ),
+        );
+
+        // Transforms between different ids can be stored together once erased.
+        let erased: Vec<DynSE3Transform<f32>> = vec![
+            se3_left_1_from_left_0.into(),
+            se3_left_from_right.at_time(0).into(),
+        ];
+        let dyn_left_1_from_right_0 = erased[0].compose_with(erased[1]);
+        assert!(dyn_left_1_from_right_0.dst() == left_se3_at_1.into());
+        assert!(dyn_left_1_from_right_0.src() == right_se3_at_0.into());
+
+        let typed_left_1_from_right_0 =
+            se3_left_1_from_left_0.compose_with(se3_left_from_right.at_time(0));
+        let point = Point::new(right_se3_at_0, Isometry3::identity());
+        let dyn_point = dyn_left_1_from_right_0.transform::<LeftCameraSE3, _>(point);
+        let typed_point = typed_left_1_from_right_0.transform(point);
+        assert_pose_near(dyn_point, typed_point, ATOL);
+
+        // Composing non-adjacent Transforms, or asking for the wrong destination id, panics.
+        let panic = std::panic::catch_unwind(|| erased[1].compose_with(erased[0]));
+        assert!(panic_message(&panic.unwrap_err())
+            .contains("(id differs: RightCameraSE3 vs LeftCameraSE3, time differs: 0 vs 1)"));
+        let panic = std::panic::catch_unwind(|| {
+            dyn_left_1_from_right_0.transform::<RightCameraSE3, _>(point)
+        });
+        assert!(panic.is_err());
+    }
+
+    #[test]
+    fn test_serde_tagging() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        let point = Point::new(
+            left_se3_at_0,
+            Isometry3::from_parts(
+                Translation3::new(0., 0., POINT_DISTANCE),
+                UnitQuaternion::from_scaled_axis(Vector3::new(0.1, 0.2, 0.3)),
+            ),
+        );
+
+        let json = serde_json::to_value(point).unwrap();
+        assert!(json["coordinate_system"]["id"] == "LeftCameraSE3");
+        assert!(json["coordinate_system"]["time"] == 0);
+
+        let deserialized: Point<LeftCameraSE3, Isometry3<f32>> =
+            serde_json::from_value(json.clone()).unwrap();
+        assert!(deserialized.coordinate_system() == left_se3_at_0);
+        assert!(
+            (deserialized.coordinates().to_homogeneous() - point.coordinates().to_homogeneous())
+                .norm()
+                < ATOL
+        );
+
+        // Deserializing into a Point with a different id fails.
+        assert!(serde_json::from_value::<Point<RightCameraSE3, Isometry3<f32>>>(json).is_err());
+    }
+
+    #[test]
+    fn test_assert_pose_near() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        let pose = Isometry3::from_parts(
+            Translation3::new(0.1, 0.2, 0.3),
+            UnitQuaternion::from_scaled_axis(Vector3::new(0.1, 0.2, 0.3)),
+        );
+        let a = Point::new(left_se3_at_0, pose);
+        let b = a.map_coordinates(|coordinates| coordinates.inverse().inverse());
+        assert_pose_near(a, b, ATOL);
+
+        let c = a.map_coordinates(|mut coordinates| {
+            coordinates.rotation = UnitQuaternion::default();
+            coordinates
+        });
+        let panic = std::panic::catch_unwind(|| assert_pose_near(a, c, ATOL));
+        assert!(panic.is_err());
+    }
+
+    #[test]
+    fn test_camera_rig() {
+        let se3_left_from_right = se3_left_from_right(UnitQuaternion::identity());
+        let left_intrinsics = left_intrinsics(0., 0.);
+        let right_intrinsics = right_intrinsics();
+
+        let rig = CameraRigBuilder::new()
+            .intrinsics(left_intrinsics)
+            .intrinsics(right_intrinsics)
+            .extrinsics(se3_left_from_right)
+            .extrinsics(se3_left_from_right.invert())
+            .build(ATOL)
+            .unwrap();
+        let queried = rig.extrinsics::<LeftCameraSE3, RightCameraSE3>().unwrap();
+        assert!(
+            (queried.transform().to_homogeneous()
+                - se3_left_from_right.transform().to_homogeneous())
+            .norm()
+                < ATOL
+        );
+        let queried = rig.extrinsics::<RightCameraSE3, LeftCameraSE3>().unwrap();
+        assert!(
+            (queried.transform().translation.vector - Vector3::new(-BASELINE, 0., 0.)).norm()
+                < ATOL
+        );
+        assert!(
+            rig.intrinsics::<RightCameraImage, RightCameraSE3>()
+                .unwrap()
+                .k()
+                == right_intrinsics.k()
+        );
+        assert!(rig
+            .intrinsics::<RightCameraImage, LeftCameraSE3>()
+            .is_none());
+
+        // Contradictory extrinsics are rejected.
+        let rig = CameraRigBuilder::new()
+            .extrinsics(se3_left_from_right)
+            .extrinsics(se3_left_from_right.compose_with(StaticSE3Transform::<
+                RightCameraSE3,
+                RightCameraSE3,
+                _,
+            >::new(
+                Isometry3::translation(BASELINE, 0., 0.),
+            )))
+            .build(ATOL);
+        assert!(matches!(
+            rig,
+            Err(CameraRigError::InconsistentExtrinsics { .. })
+        ));
+
+        // So are intrinsics with a negative focal length.
+        let rig = CameraRigBuilder::new()
+            .intrinsics(StaticProjectiveTransform::<
+                LeftCameraImage,
+                LeftCameraSE3,
+                f32,
+            >::new(Matrix3::new(
+                -1., 0., 0., 0., 1., 0., 0., 0., 1.,
+            )))
+            .build(ATOL);
+        assert!(matches!(rig, Err(CameraRigError::InvalidIntrinsics { .. })));
+    }
+
+    #[test]
+    fn test_linear_transform() {
+        define_coordinate_system_id!(StateA, RnRepr<3>);
+        define_coordinate_system_id!(StateB, RnRepr<2>);
+        define_coordinate_system_id!(StateC, RnRepr<4>);
+        let a = CoordinateSystem::<StateA, SVector<f32, 3>>::at_time(0);
+        let b = CoordinateSystem::<StateB, SVector<f32, 2>>::at_time(0);
+        let c = CoordinateSystem::<StateC, SVector<f32, 4>>::at_time(0);
+
+        #[rustfmt::skip]
+        let b_from_a = LinearTransform::new(b, a, SMatrix::<f32, 2, 3>::new(
+            1., 2., 3.,
+            4., 5., 6.,
+        ));
+        #[rustfmt::skip]
+        let c_from_b = LinearTransform::new(c, b, SMatrix::<f32, 4, 2>::new(
+            1., 0.,
+            0., 1.,
+            1., 1.,
+            2., -1.,
+        ));
+
+        let c_from_a = c_from_b.compose_with(b_from_a);
+        assert!(c_from_a.dst() == c);
+        assert!(c_from_a.src() == a);
+        assert!(c_from_a.matrix() == c_from_b.matrix() * b_from_a.matrix());
+
+        let point = Point::new(a, SVector::<f32, 3>::new(1., -1., 2.));
+        assert!(
+            (c_from_a.transform(point).coordinates()
+                - c_from_b.transform(b_from_a.transform(point)).coordinates())
+            .norm()
+                < ATOL
+        );
+    }
+
+    #[test]
+    fn test_homography_transform() {
+        let left_image_at_0 = CoordinateSystem::<LeftCameraImage, Vector2<f32>>::at_time(0);
+        let right_image_at_0 = CoordinateSystem::<RightCameraImage, Vector2<f32>>::at_time(0);
+        #[rustfmt::skip]
+        let h = Matrix3::new(
+            1.2, 0.1, 5.,
+            -0.2, 0.9, -3.,
+            0.001, 0.002, 1.,
+        );
+        let right_from_left = HomographyTransform::new(right_image_at_0, left_image_at_0, h);
+
+        let pixel = Vector2::new(10., 20.);
+        let transformed = right_from_left.transform(Point::new(left_image_at_0, pixel));
+        assert!(transformed.coordinate_system() == right_image_at_0);
+
+        let w = 0.001 * 10. + 0.002 * 20. + 1.;
+        let expected = Vector2::new(
+            (1.2 * 10. + 0.1 * 20. + 5.) / w,
+            (-0.2 * 10. + 0.9 * 20. - 3.) / w,
+        );
+        assert!((transformed.coordinates() - expected).norm() < 1e-4);
+
+        let round_trip = right_from_left.invert().transform(transformed);
+        assert!((round_trip.coordinates() - pixel).norm() < 1e-4);
+    }
+
+    #[test]
+    fn test_homography_transform_homogeneous() {
+        let left_image_at_0 = CoordinateSystem::<LeftCameraImage, Vector2<f32>>::at_time(0);
+        let right_image_at_0 = CoordinateSystem::<RightCameraImage, Vector2<f32>>::at_time(0);
+        #[rustfmt::skip]
+        let h = Matrix3::new(
+            1.2, 0.1, 5.,
+            -0.2, 0.9, -3.,
+            0.001, 0.002, 1.,
+        );
+        let right_from_left = HomographyTransform::new(right_image_at_0, left_image_at_0, h);
+
+        // Homogeneous coordinates are defined up to scale, so `[20, 40, 2]` is the pixel `[10, 20]`.
+        let left_homogeneous_at_0 =
+            CoordinateSystem::<LeftCameraImage, HomogeneousPixel<f32>>::at_time(0);
+        let point = Point::new(
+            left_homogeneous_at_0,
+            HomogeneousPixel::new(Vector3::new(20., 40., 2.)),
+        );
+        let transformed = right_from_left.transform_homogeneous(point);
+        let hx = h * Vector3::new(20., 40., 2.);
+        assert!((transformed.coordinates().coords() - hx).norm() < 1e-4);
+        let pixel = transformed.to_pixel().unwrap();
+        assert!(pixel.coordinate_system() == right_image_at_0);
+        assert!((pixel.coordinates() - hx.xy() / hx[2]).norm() < 1e-4);
+        // It agrees with transforming the normalized pixel.
+        let expected =
+            right_from_left.transform(Point::new(left_image_at_0, Vector2::new(10., 20.)));
+        assert!((pixel.coordinates() - expected.coordinates()).norm() < 1e-4);
+        assert!(
+            expected.to_homogeneous().coordinates()
+                == HomogeneousPixel::from_pixel(expected.coordinates())
+        );
+
+        // A Point on the line `0.001 x + 0.002 y + 1 == 0` is mapped to infinity, which is represented exactly.
+        let to_infinity = Point::new(left_image_at_0, Vector2::new(-1000., 0.)).to_homogeneous();
+        let at_infinity = right_from_left.transform_homogeneous(to_infinity);
+        assert!(at_infinity.coordinates().is_at_infinity());
+        assert!(at_infinity.to_pixel().is_none());
+        assert!(at_infinity.coordinates().coords().xy() != Vector2::zeros());
+    }
+
+    #[test]
+    fn test_homogeneous_pixel_eq_and_serde() {
+        let left_homogeneous_at_0 =
+            CoordinateSystem::<LeftCameraImage, HomogeneousPixel<f64>>::at_time(0);
+        let a = HomogeneousPixel::new(Vector3::new(20., 40., 2.));
+        let b = HomogeneousPixel::from_pixel(Vector2::new(10., 20.));
+        assert!(a == b);
+        assert!(a == HomogeneousPixel::new(Vector3::new(-10., -20., -1.)));
+        assert!(a != HomogeneousPixel::from_pixel(Vector2::new(10., 21.)));
+        let at_infinity = HomogeneousPixel::new(Vector3::new(1., 2., 0.));
+        assert!(at_infinity == HomogeneousPixel::new(Vector3::new(3., 6., 0.)));
+        assert!(at_infinity != a);
+        // As Points, scaled copies are equal and hash alike too.
+        let points: std::collections::HashSet<_> = [a, b]
+            .into_iter()
+            .map(|pixel| Point::new(left_homogeneous_at_0, pixel))
+            .collect();
+        assert_eq!(points.len(), 1);
+
+        let json = serde_json::to_string(&a).unwrap();
+        assert!(serde_json::from_str::<HomogeneousPixel<f64>>(&json).unwrap() == a);
+        let error = serde_json::from_str::<HomogeneousPixel<f64>>(r#"{"coords":[0.0,0.0,0.0]}"#)
+            .unwrap_err();
+        assert!(error.to_string().contains("must not all be zero"));
+    }
+
+    #[test]
+    fn test_homography_from_points() {
+        let left_image_at_0 = CoordinateSystem::<LeftCameraImage, Vector2<f32>>::at_time(0);
+        let right_image_at_0 = CoordinateSystem::<RightCameraImage, Vector2<f32>>::at_time(0);
+        #[rustfmt::skip]
+        let h = Matrix3::new(
+            1.2, 0.1, 5.,
+            -0.2, 0.9, -3.,
+            0.001, 0.002, 1.,
+        );
+        let right_from_left = HomographyTransform::new(right_image_at_0, left_image_at_0, h);
+
+        let src_points = [
+            Vector2::new(0., 0.),
+            Vector2::new(100., 0.),
+            Vector2::new(100., 80.),
+            Vector2::new(0., 80.),
+            Vector2::new(40., 30.),
+        ];
+        let dst_points: Vec<_> = src_points
+            .iter()
+            .map(|p| {
+                right_from_left
+                    .transform(Point::new(left_image_at_0, *p))
+                    .coordinates()
+            })
+            .collect();
+
+        let estimated =
+            homography_from_points(right_image_at_0, left_image_at_0, &dst_points, &src_points)
+                .unwrap();
+        assert!((estimated.h() - h).norm() < 1e-3);
+
+        assert!(matches!(
+            homography_from_points(
+                right_image_at_0,
+                left_image_at_0,
+                &dst_points[..3],
+                &src_points[..3]
+            ),
+            Err(HomographyError::TooFewPoints(3))
+        ));
+        let collinear: Vec<_> = (0..5)
+            .map(|i| Vector2::new(i as f32, 2. * i as f32))
+            .collect();
+        assert!(matches!(
+            homography_from_points(right_image_at_0, left_image_at_0, &dst_points, &collinear),
+            Err(HomographyError::Degenerate)
+        ));
+    }
+
+    #[test]
+    fn test_transform_iter() {
+        let se3_left_from_right = se3_left_from_right(UnitQuaternion::from_scaled_axis(
+            Vector3::new(0.1, 0.2, 0.3),
+        ));
+        let right_se3_at_0 = CoordinateSystem::<RightCameraSE3, Isometry3<f32>>::at_time(0);
+        let points = (0..10).map(|i| {
+            Point::new(
+                right_se3_at_0,
+                Isometry3::translation(i as f32, 0., POINT_DISTANCE),
+            )
+        });
+
+        let transform = se3_left_from_right.at_time(0);
+        let eager: Vec<_> = points.clone().map(|p| transform.transform(p)).collect();
+        let lazy: Vec<_> = transform.transform_iter(points).collect();
+        assert!(eager.len() == lazy.len());
+        for (a, b) in eager.iter().zip(lazy.iter()) {
+            assert!(a.coordinate_system() == b.coordinate_system());
+            assert!(
+                (a.coordinates().to_homogeneous() - b.coordinates().to_homogeneous()).norm() < ATOL
+            );
+        }
+
+        // The first Point is always checked, even in release builds.
+        let right_se3_at_1 = CoordinateSystem::<RightCameraSE3, Isometry3<f32>>::at_time(1);
+        let panic = std::panic::catch_unwind(|| {
+            transform
+                .transform_iter([Point::new(right_se3_at_1, Isometry3::identity())])
+                .count()
+        });
+        assert!(panic.is_err());
+        // The rest are checked in debug builds.
+        let panic = std::panic::catch_unwind(|| {
+            transform
+                .transform_iter([
+                    Point::new(right_se3_at_0, Isometry3::identity()),
+                    Point::new(right_se3_at_1, Isometry3::identity()),
+                ])
+                .count()
+        });
+        assert_eq!(panic.is_err(), cfg!(debug_assertions));
+    }
+
+    #[test]
+    fn test_camera_rig_iter_sorted() {
+        define_coordinate_system_id!(ImuSE3, SE3Repr);
+        define_coordinate_system_id!(RearCameraSE3, SE3Repr);
+
+        let extrinsics = |x| Isometry3::translation(x, 0., 0.);
+        let left_from_right =
+            StaticSE3Transform::<LeftCameraSE3, RightCameraSE3, f32>::new(extrinsics(0.1));
+        let imu_from_left = StaticSE3Transform::<ImuSE3, LeftCameraSE3, f32>::new(extrinsics(0.2));
+        let rear_from_imu = StaticSE3Transform::<RearCameraSE3, ImuSE3, f32>::new(extrinsics(0.3));
+
+        let forwards = CameraRigBuilder::new()
+            .extrinsics(left_from_right)
+            .extrinsics(imu_from_left)
+            .extrinsics(rear_from_imu)
+            .build(ATOL)
+            .unwrap();
+        let backwards = CameraRigBuilder::new()
+            .extrinsics(rear_from_imu)
+            .extrinsics(imu_from_left)
+            .extrinsics(left_from_right)
+            .build(ATOL)
+            .unwrap();
+
+        let forwards: Vec<_> = forwards
+            .iter_sorted()
+            .map(|(dst, src, _)| (dst, src))
+            .collect();
+        let backwards: Vec<_> = backwards
+            .iter_sorted()
+            .map(|(dst, src, _)| (dst, src))
+            .collect();
+        assert!(forwards == backwards);
+        assert!(
+            forwards
+                == vec![
+                    ("ImuSE3", "LeftCameraSE3"),
+                    ("ImuSE3", "RearCameraSE3"),
+                    ("LeftCameraSE3", "ImuSE3"),
+                    ("LeftCameraSE3", "RightCameraSE3"),
+                    ("RearCameraSE3", "ImuSE3"),
+                    ("RightCameraSE3", "LeftCameraSE3"),
+                ]
+        );
+    }
+
+    #[test]
+    fn test_unprojective_transform() {
+        let left_intrinsics = left_intrinsics(12., -7.);
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        let point = Point::new(
+            left_se3_at_0,
+            Isometry3::translation(0.1, -0.2, POINT_DISTANCE),
+        );
+
+        let projection = left_intrinsics.at_time(0);
+        let unprojection = projection.invert();
+        assert!(unprojection.dst() == projection.src());
+        assert!(unprojection.src() == projection.dst());
+
+        let pixel = projection.transform(point);
+        let recovered = unprojection.transform_with_depth(pixel, POINT_DISTANCE);
+        assert!(recovered.coordinate_system() == left_se3_at_0);
+        assert!(
+            (recovered.coordinates().translation.vector - point.coordinates().translation.vector)
+                .norm()
+                < ATOL
+        );
+
+        let bearing = unprojection.bearing(pixel);
+        assert!((bearing - point.coordinates().translation.vector.normalize()).norm() < ATOL);
+    }
+
+    #[test]
+    fn test_with_time() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        let left_se3_at_5 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(5);
+        assert!(left_se3_at_0.with_time(5) == left_se3_at_5);
+
+        let pose = Isometry3::from_parts(
+            Translation3::new(0.1, 0.2, 0.3),
+            UnitQuaternion::from_scaled_axis(Vector3::new(0.1, 0.2, 0.3)),
+        );
+        let point = Point::new(left_se3_at_0, pose);
+        let rebased = point.with_time(5);
+        assert!(rebased.coordinate_system() == left_se3_at_5);
+        assert!(rebased.coordinates() == pose);
+    }
+
+    #[test]
+    fn test_project_batch() {
+        let left_intrinsics = left_intrinsics(0., 0.);
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        // In front, behind, in the plane of, and far in front of the Camera; then in front, but to the
+        // right of, or to the left of, a 64 x 48 image.
+        let points: Vec<_> = [
+            (BASELINE, POINT_DISTANCE),
+            (BASELINE, -POINT_DISTANCE),
+            (BASELINE, 0.),
+            (BASELINE, 2. * POINT_DISTANCE),
+            (POINT_DISTANCE, POINT_DISTANCE),
+            (-BASELINE, POINT_DISTANCE),
+        ]
+        .iter()
+        .map(|(x, z)| Point::new(left_se3_at_0, Isometry3::translation(*x, 0., *z)))
+        .collect();
+
+        let projection = left_intrinsics.at_time(0);
+        let projected = projection.project_batch(&points, 64, 48);
+        let visible: Vec<_> = projected.iter().map(Option::is_some).collect();
+        assert!(visible == vec![true, false, false, true, false, false]);
+        for (point, pixel) in points.iter().zip(projected.iter()) {
+            if let Some(pixel) = pixel {
+                assert!(projection.project_if_visible(*point) == Some(*pixel));
+                assert!(
+                    (pixel.coordinates() - projection.transform(*point).coordinates()).norm()
+                        < ATOL
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_squared_distance() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        let a = Point::new(left_se3_at_0, Isometry3::translation(0.1, 0.2, 0.3));
+        let b = Point::new(
+            left_se3_at_0,
+            Isometry3::from_parts(
+                Translation3::new(-0.3, 0.5, 0.1),
+                UnitQuaternion::from_scaled_axis(Vector3::new(0.1, 0.2, 0.3)),
+            ),
+        );
+        assert!(a.position() == Vector3::new(0.1, 0.2, 0.3));
+        let expected = (a.coordinates().translation.vector - b.coordinates().translation.vector)
+            .norm()
+            .powi(2);
+        assert!((a.squared_distance(&b) - expected).abs() < ATOL);
+        assert!((a.squared_distance(&b) - b.squared_distance(&a)).abs() < ATOL);
+    }
+
+    #[test]
+    fn test_projection_pipeline() {
+        let se3_left_from_right = se3_left_from_right(UnitQuaternion::from_scaled_axis(
+            Vector3::new(0.01, 0.02, 0.03),
+        ));
+        let left_intrinsics = left_intrinsics(0., 0.);
+        let right_se3_at_0 = CoordinateSystem::<RightCameraSE3, Isometry3<f32>>::at_time(0);
+        let left_image_at_0 = CoordinateSystem::<LeftCameraImage, Vector2<f32>>::at_time(0);
+
+        let pipeline =
+            ProjectionPipeline::new(se3_left_from_right.at_time(0), left_intrinsics.at_time(0));
+        let point = Point::new(
+            right_se3_at_0,
+            Isometry3::translation(0.05, -0.1, POINT_DISTANCE),
+        );
+
+        let pixel = pipeline.project(point);
+        let expected = left_intrinsics
+            .at_time(0)
+            .transform(se3_left_from_right.at_time(0).transform(point));
+        assert!(pixel.coordinate_system() == left_image_at_0);
+        assert!((pixel.coordinates() - expected.coordinates()).norm() < ATOL);
+    }
+
+    #[test]
+    fn test_scale_transform() {
+        define_coordinate_system_id!(Metric, RnRepr<3>);
+        define_coordinate_system_id!(Voxel, RnRepr<3>);
+        let metric_at_0 = CoordinateSystem::<Metric, Vector3<f32>>::at_time(0);
+        let voxel_at_0 = CoordinateSystem::<Voxel, Vector3<f32>>::at_time(0);
+
+        let voxel_from_metric =
+            ScaleTransform::new(voxel_at_0, metric_at_0, Vector3::new(10., 20., 5.));
+        let point = Point::new(metric_at_0, Vector3::new(0.1, -0.2, 0.3));
+        let scaled = voxel_from_metric.transform(point);
+        assert!(scaled.coordinate_system() == voxel_at_0);
+        assert!((scaled.coordinates() - Vector3::new(1., -4., 1.5)).norm() < ATOL);
+
+        let recovered = voxel_from_metric.invert().transform(scaled);
+        assert!(recovered.coordinate_system() == metric_at_0);
+        assert!((recovered.coordinates() - point.coordinates()).norm() < ATOL);
+
+        let identity = voxel_from_metric.invert().compose_with(voxel_from_metric);
+        assert!((identity.scale() - Vector3::new(1., 1., 1.)).norm() < ATOL);
+
+        let degenerate = ScaleTransform::new(voxel_at_0, metric_at_0, Vector3::new(1., 0., 1.));
+        let panic = std::panic::catch_unwind(|| degenerate.invert());
         assert!(panic.is_err());
     }
+
+    #[test]
+    fn test_se3_transform_mul() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        let left_se3_at_1 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(1);
+        let se3_left_1_from_left_0 = SE3Transform::new(
+            left_se3_at_1,
+            left_se3_at_0,
+            Isometry3::from_parts(
+                Translation3::new(0.1, 0.2, 0.3),
+                UnitQuaternion::from_scaled_axis(Vector3::new(0.1, 0.2, 0.3)),
+            ),
+        );
+        let point = Point::new(
+            left_se3_at_0,
+            Isometry3::translation(0., 0., POINT_DISTANCE),
+        );
+
+        let transformed = se3_left_1_from_left_0 * point;
+        assert!(transformed.coordinate_system() == left_se3_at_1);
+        assert_pose_near(transformed, se3_left_1_from_left_0.transform(point), ATOL);
+
+        let panic = std::panic::catch_unwind(|| se3_left_1_from_left_0 * transformed);
+        assert_eq!(panic.is_err(), cfg!(debug_assertions));
+    }
+
+    #[test]
+    fn test_timed_transform() {
+        let se3_left_from_right = se3_left_from_right(UnitQuaternion::identity());
+        let timed = TimedTransform::new(se3_left_from_right, (0, 10));
+
+        assert!(timed.contains(0) && timed.contains(10));
+        let at_5 = timed.at_time(5).unwrap();
+        assert!(at_5.dst() == CoordinateSystem::at_time(5));
+        assert!(at_5.src() == CoordinateSystem::at_time(5));
+        assert!(
+            timed.at_time(11).unwrap_err()
+                == OutOfRangeError {
+                    time: 11,
+                    valid_range: (0, 10)
+                }
+        );
+    }
+
+    #[test]
+    fn test_bilinear_sample() {
+        #[rustfmt::skip]
+        let image = DMatrix::from_row_slice(2, 3, &[
+            0f32, 1., 2.,
+            10., 11., 12.,
+        ]);
+        assert!(bilinear_sample(&image, Vector2::new(0., 0.)) == Some(0.));
+        assert!(bilinear_sample(&image, Vector2::new(2., 1.)) == Some(12.));
+        assert!((bilinear_sample(&image, Vector2::new(0.5, 0.)).unwrap() - 0.5).abs() < ATOL);
+        assert!((bilinear_sample(&image, Vector2::new(1.5, 0.5)).unwrap() - 6.5).abs() < ATOL);
+        assert!(bilinear_sample(&image, Vector2::new(-0.1, 0.)).is_none());
+        assert!(bilinear_sample(&image, Vector2::new(0., 1.5)).is_none());
+    }
+
+    #[test]
+    fn test_serde_isometry() {
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Config {
+            #[serde(with = "serde_isometry")]
+            extrinsics: Isometry3<f32>,
+        }
+
+        let extrinsics = Isometry3::from_parts(
+            Translation3::new(BASELINE, 0.2, 0.3),
+            UnitQuaternion::from_scaled_axis(Vector3::new(0.1, 0.2, 0.3)),
+        );
+        let config = Config { extrinsics };
+
+        let json = serde_json::to_value(&config).unwrap();
+        let q = extrinsics.rotation.coords;
+        assert!(
+            json["extrinsics"]
+                == serde_json::json!([BASELINE, 0.2f32, 0.3f32, q[0], q[1], q[2], q[3]])
+        );
+        let from_json: Config = serde_json::from_value(json).unwrap();
+        assert!(
+            (from_json.extrinsics.to_homogeneous() - extrinsics.to_homogeneous()).norm() < ATOL
+        );
+
+        let bytes = bincode::serialize(&config).unwrap();
+        assert!(bytes == bincode::serialize(&extrinsics).unwrap());
+        let from_bincode: Config = bincode::deserialize(&bytes).unwrap();
+        assert!(from_bincode.extrinsics == extrinsics);
+    }
+
+    #[test]
+    fn test_point_eq() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        let pose = || {
+            Isometry3::from_parts(
+                Translation3::new(0.1, 0.2, 0.3),
+                UnitQuaternion::from_scaled_axis(Vector3::new(0.1, 0.2, 0.3)),
+            )
+        };
+        let a = Point::new(left_se3_at_0, pose());
+        let b = Point::new(left_se3_at_0, pose());
+        assert_eq!(a, b);
+        assert_ne!(a, a.with_time(1));
+        assert_ne!(
+            a,
+            a.map_coordinates(|c| c * Isometry3::translation(ATOL, 0., 0.))
+        );
+
+        // `f64` Points are `Eq` and `Hash` over their bit patterns, so they can be used as keys.
+        define_coordinate_system_id!(WorldR3, RnRepr<3>);
+        let world_at_0 = CoordinateSystem::<WorldR3, Vector3<f64>>::at_time(0);
+        let c = Point::new(world_at_0, Vector3::new(0.1, 0.2, 0.3));
+        let points: std::collections::HashSet<_> = [
+            c,
+            Point::new(world_at_0, Vector3::new(0.1, 0.2, 0.3)),
+            c.with_time(1),
+            c.map_coordinates(|v| v.map(|x: f64| -x)),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(points.len(), 3);
+        assert!(points.contains(&c));
+        // Exact over bit patterns, so `0.0` and `-0.0` differ.
+        let zero = Point::new(world_at_0, Vector3::new(0., 0., 0.));
+        assert_ne!(zero, zero.map_coordinates(|v| -v));
+    }
+
+    #[test]
+    fn test_handedness() {
+        define_coordinate_system_id!(GraphicsSE3, SE3Repr, Handedness::Left);
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        let graphics_se3_at_0 = CoordinateSystem::<GraphicsSE3, Isometry3<f32>>::at_time(0);
+
+        // A rigid-body Transform cannot change handedness.
+        let panic = std::panic::catch_unwind(|| {
+            SE3Transform::new(
+                graphics_se3_at_0,
+                left_se3_at_0,
+                Isometry3::<f32>::identity(),
+            )
+        });
+        assert!(panic.is_err());
+        let panic = std::panic::catch_unwind(|| {
+            StaticSE3Transform::<GraphicsSE3, LeftCameraSE3, f32>::new(Isometry3::identity())
+        });
+        assert!(panic.is_err());
+
+        // Instead, flip the z-axis explicitly.
+        let graphics_from_left = HandednessFlip::new(graphics_se3_at_0, left_se3_at_0, 2);
+        let point = Point::new(
+            left_se3_at_0,
+            Isometry3::from_parts(
+                Translation3::new(0.1, 0.2, POINT_DISTANCE),
+                UnitQuaternion::from_scaled_axis(Vector3::new(0.1, 0.2, 0.3)),
+            ),
+        );
+        let flipped = graphics_from_left.transform(point);
+        assert!(flipped.coordinate_system() == graphics_se3_at_0);
+        assert!(
+            (flipped.coordinates().translation.vector - Vector3::new(0.1, 0.2, -POINT_DISTANCE))
+                .norm()
+                < ATOL
+        );
+        let recovered = graphics_from_left.invert().transform(flipped);
+        assert_pose_near(recovered, point, ATOL);
+
+        let panic = std::panic::catch_unwind(|| {
+            HandednessFlip::<LeftCameraSE3, LeftCameraSE3, f32>::new(
+                left_se3_at_0,
+                left_se3_at_0,
+                2,
+            )
+        });
+        assert!(panic.is_err());
+    }
+
+    #[test]
+    fn test_identity_transform_fast_path() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        let right_se3_at_0 = CoordinateSystem::<RightCameraSE3, Isometry3<f32>>::at_time(0);
+        let right_from_left =
+            SE3Transform::new(right_se3_at_0, left_se3_at_0, Isometry3::identity());
+        assert!(right_from_left.is_identity());
+        let not_identity = SE3Transform::new(
+            right_se3_at_0,
+            left_se3_at_0,
+            Isometry3::translation(BASELINE, 0., 0.),
+        );
+        assert!(!not_identity.is_identity());
+        // Tiny motions are not identity, e.g. a millimetre at a kilometre of range in f32.
+        let tiny = SE3Transform::new(
+            right_se3_at_0,
+            left_se3_at_0,
+            Isometry3::from_parts(
+                Translation3::new(f32::EPSILON, 0., 0.),
+                UnitQuaternion::from_scaled_axis(Vector3::new(0., 1e-6, 0.)),
+            ),
+        );
+        assert!(!tiny.is_identity());
+        let far = Point::new(left_se3_at_0, Isometry3::translation(0., 0., 1000.));
+        assert!(
+            (tiny.transform(far).coordinates().translation.vector
+                - far.coordinates().translation.vector)
+                .norm()
+                > 5e-4
+        );
+
+        let points: Vec<_> = (0..4)
+            .map(|i| {
+                Point::new(
+                    left_se3_at_0,
+                    Isometry3::from_parts(
+                        Translation3::new(0.1 * i as f32, 0.2, POINT_DISTANCE),
+                        UnitQuaternion::from_scaled_axis(Vector3::new(0.1, 0.2, 0.3 * i as f32)),
+                    ),
+                )
+            })
+            .collect();
+        for (transformed, point) in right_from_left
+            .transform_iter(points.iter().copied())
+            .zip(&points)
+        {
+            assert!(transformed.coordinate_system() == right_se3_at_0);
+            assert_eq!(transformed.coordinates(), point.coordinates());
+        }
+    }
+
+    #[test]
+    fn test_projection_jacobian_matches_finite_differences() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f64>>::at_time(0);
+        let left_image_at_0 = CoordinateSystem::<LeftCameraImage, Vector2<f64>>::at_time(0);
+        #[rustfmt::skip]
+        let k = Matrix3::new(
+            LEFT_FOCAL_LEN as f64, 0., 320.,
+            0., LEFT_FOCAL_LEN as f64, 240.,
+            0., 0., 1.,
+        );
+        let projection = ProjectiveTransform::new(left_image_at_0, left_se3_at_0, k);
+        let project = |x: Vector3<f64>| {
+            projection
+                .transform(Point::new(
+                    left_se3_at_0,
+                    Isometry3::translation(x[0], x[1], x[2]),
+                ))
+                .coordinates()
+        };
+
+        let x = Vector3::new(0.1, -0.2, POINT_DISTANCE as f64);
+        let (fx, fy) = (k[(0, 0)], k[(1, 1)]);
+        #[rustfmt::skip]
+        let analytic = nalgebra::Matrix2x3::new(
+            fx / x[2], 0., -fx * x[0] / (x[2] * x[2]),
+            0., fy / x[2], -fy * x[1] / (x[2] * x[2]),
+        );
+        assert_jacobian_matches(analytic, project, x, 1e-4);
+
+        let panic =
+            std::panic::catch_unwind(|| assert_jacobian_matches(analytic * 2., project, x, 1e-4));
+        assert!(panic.is_err());
+    }
+
+    #[test]
+    fn test_projective_transform_from_physical() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f64>>::at_time(0);
+        let left_image_at_0 = CoordinateSystem::<LeftCameraImage, Vector2<f64>>::at_time(0);
+        // 35mm lens on a 36mm x 24mm sensor at 6000 x 3000 px: pixels are 6um wide and 8um tall.
+        let projection = ProjectiveTransform::from_physical(
+            left_image_at_0,
+            left_se3_at_0,
+            35.,
+            36.,
+            24.,
+            6000,
+            3000,
+        );
+        #[rustfmt::skip]
+        let expected = Matrix3::new(
+            35. / 0.006, 0., 3000.,
+            0., 35. / 0.008, 1500.,
+            0., 0., 1.,
+        );
+        assert!((projection.k() - expected).norm() < 1e-6 * expected.norm());
+
+        let panic = std::panic::catch_unwind(|| {
+            ProjectiveTransform::from_physical(
+                left_image_at_0,
+                left_se3_at_0,
+                35.,
+                0.,
+                24.,
+                6000,
+                3000,
+            )
+        });
+        assert!(panic.is_err());
+    }
+
+    #[test]
+    fn test_point3_round_trip() {
+        define_coordinate_system_id!(WorldR3, RnRepr<3>);
+        let world_at_0 = CoordinateSystem::<WorldR3, Vector3<f32>>::at_time(0);
+        let point = Point::new(world_at_0, Vector3::new(0.1, 0.2, POINT_DISTANCE));
+        let point3 = point.to_point3();
+        assert_eq!(point3, nalgebra::Point3::new(0.1, 0.2, POINT_DISTANCE));
+        assert!(Point::from_point3(world_at_0, point3) == point);
+
+        let vector = Vector3::from(point);
+        assert_eq!(vector, Vector3::new(0.1, 0.2, POINT_DISTANCE));
+        assert!(Point::from_vector(world_at_0, vector) == point);
+    }
+
+    #[test]
+    fn test_se3_transform_lerp_to() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        let right_se3_at_0 = CoordinateSystem::<RightCameraSE3, Isometry3<f32>>::at_time(0);
+        let start = SE3Transform::new(
+            right_se3_at_0,
+            left_se3_at_0,
+            Isometry3::translation(BASELINE, 0., 0.),
+        );
+        let end = SE3Transform::new(
+            right_se3_at_0.with_time(10),
+            left_se3_at_0.with_time(10),
+            Isometry3::from_parts(
+                Translation3::new(BASELINE, 0.02, 0.),
+                UnitQuaternion::from_scaled_axis(Vector3::new(0., 0.1, 0.)),
+            ),
+        );
+
+        let at_start = start.lerp_to(&end, 0.);
+        assert!(at_start.src() == start.src());
+        assert_pose_near(as_pose(at_start), as_pose(start), ATOL);
+        let at_end = start.lerp_to(&end, 1.);
+        assert!(at_end.src() == end.src());
+        assert_pose_near(as_pose(at_end), as_pose(end), ATOL);
+
+        // Halfway along the screw motion, applying the relative motion twice reaches `end`.
+        let halfway = start.lerp_to(&end, 0.5);
+        assert!(halfway.dst() == right_se3_at_0.with_time(5));
+        assert!((halfway.isometry().rotation.angle() - 0.05).abs() < ATOL);
+        let half_step = start.isometry().inverse() * halfway.isometry();
+        assert_pose_near(
+            Point::new(end.dst(), start.isometry() * half_step * half_step),
+            as_pose(end),
+            ATOL,
+        );
+
+        // Times beyond 2^53 are interpolated exactly, in either direction.
+        let late = SE3Transform::new(
+            right_se3_at_0.with_time((1 << 60) + 1),
+            left_se3_at_0.with_time((1 << 60) + 3),
+            start.isometry(),
+        );
+        let later = SE3Transform::new(
+            right_se3_at_0.with_time((1 << 60) + 3),
+            left_se3_at_0.with_time((1 << 60) + 1),
+            start.isometry(),
+        );
+        let halfway = late.lerp_to(&later, 0.5);
+        assert_eq!(halfway.dst().time(), (1 << 60) + 2);
+        assert_eq!(halfway.src().time(), (1 << 60) + 2);
+    }
+
+    #[test]
+    fn test_se3_transform_rotation() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        let right_se3_at_0 = CoordinateSystem::<RightCameraSE3, Isometry3<f32>>::at_time(0);
+        let rotation = UnitQuaternion::from_scaled_axis(Vector3::new(0.1, 0.2, 0.3));
+        let right_from_left = SE3Transform::new(
+            right_se3_at_0,
+            left_se3_at_0,
+            Isometry3::from_parts(Translation3::new(BASELINE, 0., 0.), rotation),
+        );
+        assert_eq!(right_from_left.rotation(), rotation);
+        assert_eq!(
+            right_from_left.rotation(),
+            right_from_left.isometry().rotation
+        );
+        assert_rotation_near(
+            right_from_left.invert().rotation(),
+            rotation.inverse(),
+            ATOL,
+        );
+    }
+
+    #[test]
+    fn test_columnar_poses_round_trip() {
+        let points: Vec<_> = (0..10_000u64)
+            .map(|i| {
+                let x = i as f32 * 1e-3;
+                Point::new(
+                    CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(i),
+                    Isometry3::from_parts(
+                        Translation3::new(x, -x, POINT_DISTANCE),
+                        UnitQuaternion::from_scaled_axis(Vector3::new(0.1, x, 0.3)),
+                    ),
+                )
+            })
+            .collect();
+        let bytes = encode_poses(&points);
+        assert_eq!(
+            bytes.len(),
+            4 + "LeftCameraSE3".len() + 8 + points.len() * (8 + 7 * 4)
+        );
+        let decoded = decode_poses::<LeftCameraSE3>(&bytes).unwrap();
+        assert!(decoded == points);
+
+        assert_eq!(
+            decode_poses::<LeftCameraSE3>(&bytes[..bytes.len() - 1]),
+            Err(ColumnarError::Truncated)
+        );
+        assert_eq!(
+            decode_poses::<RightCameraSE3>(&bytes),
+            Err(ColumnarError::MismatchedId {
+                expected: "RightCameraSE3",
+                found: "LeftCameraSE3".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_static_tree() {
+        define_coordinate_system_id!(ImuSE3, SE3Repr);
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        let right_se3_at_0 = CoordinateSystem::<RightCameraSE3, Isometry3<f32>>::at_time(0);
+        let imu_se3_at_0 = CoordinateSystem::<ImuSE3, Isometry3<f32>>::at_time(0);
+        let right_from_left = SE3Transform::new(
+            right_se3_at_0,
+            left_se3_at_0,
+            Isometry3::translation(-BASELINE, 0., 0.),
+        );
+        let imu_from_right = SE3Transform::new(
+            imu_se3_at_0,
+            right_se3_at_0,
+            Isometry3::from_parts(
+                Translation3::new(0., 0.05, 0.),
+                UnitQuaternion::from_scaled_axis(Vector3::new(0., 0.1, 0.)),
+            ),
+        );
+        let left_from_imu = imu_from_right.compose_with(right_from_left).invert();
+
+        let mut transforms: Vec<DynSE3Transform<f32>> = vec![
+            right_from_left.into(),
+            imu_from_right.into(),
+            left_from_imu.into(),
+        ];
+        assert_eq!(validate_static_tree(&transforms, 1e-5), Ok(()));
+
+        // A typo in one edge breaks the cycle. Every edge of the cycle is reported, since any of them may be wrong.
+        transforms[2] = SE3Transform::new(
+            left_se3_at_0,
+            imu_se3_at_0,
+            left_from_imu.isometry() * Isometry3::translation(0.01, 0., 0.),
+        )
+        .into();
+        let error = validate_static_tree(&transforms, 1e-5).unwrap_err();
+        assert_eq!(
+            error.cycle,
+            vec![
+                right_se3_at_0.into(),
+                left_se3_at_0.into(),
+                imu_se3_at_0.into()
+            ]
+        );
+        assert_eq!(
+            error.to_string(),
+            "Transforms around the cycle RightCameraSE3@0 -> LeftCameraSE3@0 -> ImuSE3@0 -> RightCameraSE3@0 do not compose to identity."
+        );
+    }
+
+    #[test]
+    fn test_project_with_jacobian() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f64>>::at_time(0);
+        let left_image_at_0 = CoordinateSystem::<LeftCameraImage, Vector2<f64>>::at_time(0);
+        #[rustfmt::skip]
+        let k = Matrix3::new(
+            LEFT_FOCAL_LEN as f64, 0., 320.,
+            0., LEFT_FOCAL_LEN as f64, 240.,
+            0., 0., 1.,
+        );
+        let projection = ProjectiveTransform::new(left_image_at_0, left_se3_at_0, k);
+        let point = Point::new(
+            left_se3_at_0,
+            Isometry3::translation(0.1, -0.2, POINT_DISTANCE as f64),
+        );
+
+        let (pixel, jacobian_wrt_pose, jacobian_wrt_point) =
+            projection.project_with_jacobian(point);
+        assert!(pixel == projection.transform(point));
+
+        let project = |x: Vector3<f64>| {
+            projection
+                .transform(Point::new(
+                    left_se3_at_0,
+                    Isometry3::translation(x[0], x[1], x[2]),
+                ))
+                .coordinates()
+        };
+        assert_jacobian_matches(jacobian_wrt_point, project, point.position(), 1e-4);
+
+        // Left-perturb the Point's pose by the rotation (first 3) or translation (last 3) part of δ.
+        let perturb = |delta: nalgebra::Vector6<f64>| {
+            let perturbation = Isometry3::new(
+                delta.fixed_rows::<3>(3).into(),
+                delta.fixed_rows::<3>(0).into(),
+            );
+            project((perturbation * point.coordinates()).translation.vector)
+        };
+        for offset in [0, 3] {
+            let analytic = jacobian_wrt_pose.fixed_columns::<3>(offset).into_owned();
+            let f = |x: Vector3<f64>| {
+                let mut delta = nalgebra::Vector6::zeros();
+                delta.fixed_rows_mut::<3>(offset).copy_from(&x);
+                perturb(delta)
+            };
+            assert_jacobian_matches(analytic, f, Vector3::zeros(), 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_frame_cloud() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        let right_se3_at_0 = CoordinateSystem::<RightCameraSE3, Isometry3<f32>>::at_time(0);
+        let positions: Vec<_> = (0..10)
+            .flat_map(|i| {
+                (0..10).map(move |j| Vector3::new(0.1 * i as f32, 0.1 * j as f32, POINT_DISTANCE))
+            })
+            .collect();
+        let cloud = FrameCloud::new(left_se3_at_0, positions, 0.25);
+
+        let center = Point::new(
+            left_se3_at_0,
+            Isometry3::translation(0.2, 0.2, POINT_DISTANCE),
+        );
+        let neighbors = cloud.radius_query(center, 0.11);
+        assert_eq!(neighbors, vec![12, 21, 22, 23, 32]);
+
+        // A huge radius only visits the occupied cells (or scans), instead of every cell it covers.
+        assert_eq!(
+            cloud.radius_query(center, 1e6),
+            (0..100).collect::<Vec<_>>()
+        );
+        let far = Point::new(left_se3_at_0, Isometry3::translation(1e6, 0., 0.));
+        assert!(cloud.radius_query(far, 1.).is_empty());
+        let empty = FrameCloud::new(left_se3_at_0, Vec::new(), 0.25);
+        assert!(empty.radius_query(center, 1.).is_empty());
+        for (center, radius) in [
+            (
+                Point::new(left_se3_at_0, Isometry3::translation(f32::NAN, 0., 0.)),
+                1.,
+            ),
+            (center, f32::INFINITY),
+            (center, -1.),
+        ] {
+            let panic = std::panic::catch_unwind(|| cloud.radius_query(center, radius));
+            assert!(panic_message(&panic.unwrap_err()).contains("must be finite"));
+        }
+
+        let right_from_left =
+            se3_left_from_right(UnitQuaternion::from_scaled_axis(Vector3::new(0., 0., 0.3)))
+                .invert();
+        let right_cloud = cloud.transform_to(&right_from_left);
+        assert!(right_cloud.coordinate_system() == right_se3_at_0);
+        let right_center = right_from_left.at_time(0).transform(center);
+        assert_eq!(right_cloud.radius_query(right_center, 0.11), neighbors);
+    }
+
+    #[test]
+    fn test_perturbation_conventions() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f64>>::at_time(0);
+        let right_se3_at_0 = CoordinateSystem::<RightCameraSE3, Isometry3<f64>>::at_time(0);
+        let right_image_at_0 = CoordinateSystem::<RightCameraImage, Vector2<f64>>::at_time(0);
+        let right_from_left = SE3Transform::new(
+            right_se3_at_0,
+            left_se3_at_0,
+            Isometry3::from_parts(
+                Translation3::new(-BASELINE as f64, 0.02, 0.),
+                UnitQuaternion::from_scaled_axis(Vector3::new(0.1, -0.2, 0.05)),
+            ),
+        );
+
+        // Left and right increments differ, but are related by the adjoint.
+        let delta = nalgebra::Vector6::new(0.01, 0.02, -0.03, 0.1, 0., 0.05);
+        let left = right_from_left.oplus(delta, Perturbation::Left);
+        let right = right_from_left.oplus(delta, Perturbation::Right);
+        assert!(
+            (left.isometry().to_homogeneous() - right.isometry().to_homogeneous()).norm() > 1e-3
+        );
+        let adjoint_left =
+            right_from_left.oplus(right_from_left.adjoint() * delta, Perturbation::Left);
+        assert!(
+            (adjoint_left.isometry().to_homogeneous() - right.isometry().to_homogeneous()).norm()
+                < 1e-9
+        );
+
+        // Each Jacobian matches finite differences of its own convention.
+        #[rustfmt::skip]
+        let k = Matrix3::new(
+            RIGHT_FOCAL_LEN as f64, 0., 320.,
+            0., RIGHT_FOCAL_LEN as f64, 240.,
+            0., 0., 1.,
+        );
+        let projection = ProjectiveTransform::new(right_image_at_0, right_se3_at_0, k);
+        let point = Point::new(
+            left_se3_at_0,
+            Isometry3::translation(0.1, -0.2, POINT_DISTANCE as f64),
+        );
+        for perturbation in [Perturbation::Left, Perturbation::Right] {
+            let pipeline = ProjectionPipeline::new(right_from_left, projection);
+            let (pixel, jacobian_wrt_pose, jacobian_wrt_point) =
+                pipeline.project_with_jacobian(point, perturbation);
+            assert!(pixel == pipeline.project(point));
+
+            let project_point = |x: Vector3<f64>| {
+                pipeline
+                    .project(Point::new(
+                        left_se3_at_0,
+                        Isometry3::translation(x[0], x[1], x[2]),
+                    ))
+                    .coordinates()
+            };
+            assert_jacobian_matches(jacobian_wrt_point, project_point, point.position(), 1e-4);
+
+            for offset in [0, 3] {
+                let analytic = jacobian_wrt_pose.fixed_columns::<3>(offset).into_owned();
+                let project_perturbed = |x: Vector3<f64>| {
+                    let mut delta = nalgebra::Vector6::zeros();
+                    delta.fixed_rows_mut::<3>(offset).copy_from(&x);
+                    ProjectionPipeline::new(right_from_left.oplus(delta, perturbation), projection)
+                        .project(point)
+                        .coordinates()
+                };
+                assert_jacobian_matches(analytic, project_perturbed, Vector3::zeros(), 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_skewed_intrinsics_round_trip() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f64>>::at_time(0);
+        let left_image_at_0 = CoordinateSystem::<LeftCameraImage, Vector2<f64>>::at_time(0);
+        let projection = ProjectiveTransform::from_intrinsics(
+            left_image_at_0,
+            left_se3_at_0,
+            LEFT_FOCAL_LEN as f64,
+            1.1 * LEFT_FOCAL_LEN as f64,
+            320.,
+            240.,
+        )
+        .with_skew(5.);
+        assert_eq!(projection.skew(), 5.);
+
+        let position = Vector3::new(0.1, -0.2, POINT_DISTANCE as f64);
+        let point = Point::new(
+            left_se3_at_0,
+            Isometry3::translation(position[0], position[1], position[2]),
+        );
+        let pixel = projection.transform(point);
+        // Skew shifts the pixel along the x-axis in proportion to its y-coordinate.
+        let unskewed = ProjectiveTransform::from_intrinsics(
+            left_image_at_0,
+            left_se3_at_0,
+            LEFT_FOCAL_LEN as f64,
+            1.1 * LEFT_FOCAL_LEN as f64,
+            320.,
+            240.,
+        )
+        .transform(point);
+        assert!(
+            (pixel.coordinates()[0] - unskewed.coordinates()[0] - 5. * position[1] / position[2])
+                .abs()
+                < ATOL as f64
+        );
+
+        let unprojection = projection.invert();
+        assert!((unprojection.bearing(pixel) - position.normalize()).norm() < ATOL as f64);
+        let recovered = unprojection.transform_with_depth(pixel, position[2]);
+        assert!((recovered.position() - position).norm() < ATOL as f64);
+    }
+
+    #[test]
+    fn test_clock_conversion() {
+        #[derive(Debug, Default, Clone, Copy)]
+        struct SensorTicks;
+        impl IsClock for SensorTicks {}
+        const NANOS_PER_TICK: u64 = 1_000;
+
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        let right_se3_at_0 = CoordinateSystem::<RightCameraSE3, Isometry3<f32>>::at_time(0);
+        let right_from_left = SE3Transform::new(
+            right_se3_at_0.with_time(5 * NANOS_PER_TICK),
+            left_se3_at_0.with_time(5 * NANOS_PER_TICK),
+            Isometry3::translation(-BASELINE, 0., 0.),
+        );
+
+        // A Point stamped by the sensor's tick counter must be converted before it can be transformed.
+        let point = Point::new(
+            CoordinateSystem::<LeftCameraSE3, Isometry3<f32>, SensorTicks>::at_time(5),
+            Isometry3::translation(0., 0., POINT_DISTANCE),
+        );
+        let converted = point.convert_clock(|ticks| ticks * NANOS_PER_TICK);
+        assert!(converted.coordinate_system() == left_se3_at_0.with_time(5 * NANOS_PER_TICK));
+        let transformed = right_from_left.transform(converted);
+        assert!(transformed.coordinate_system() == right_se3_at_0.with_time(5 * NANOS_PER_TICK));
+    }
+
+    #[test]
+    fn test_se3_exp_small_angles() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f64>>::at_time(0);
+        let right_se3_at_0 = CoordinateSystem::<RightCameraSE3, Isometry3<f64>>::at_time(0);
+        let identity = SE3Transform::new(right_se3_at_0, left_se3_at_0, Isometry3::identity());
+        // Sweep θ across the Taylor-series threshold, comparing against the matrix exponential of the twist.
+        for i in 0..=80 {
+            let theta = 10f64.powf(-8. + 0.1 * i as f64);
+            let axis = Vector3::new(1., -2., 3.).normalize();
+            let omega = axis * theta;
+            let v = Vector3::new(0.3, 0.2, -0.1);
+            let delta = nalgebra::Vector6::new(omega[0], omega[1], omega[2], v[0], v[1], v[2]);
+            let mut twist = nalgebra::Matrix4::zeros();
+            twist
+                .fixed_slice_mut::<3, 3>(0, 0)
+                .copy_from(&omega.cross_matrix());
+            twist.fixed_slice_mut::<3, 1>(0, 3).copy_from(&v);
+            let expected = twist.exp();
+            let actual = identity
+                .oplus(delta, Perturbation::Left)
+                .isometry()
+                .to_homogeneous();
+            assert!(
+                (actual - expected).norm() < 1e-12,
+                "exp error {} at theta {}",
+                (actual - expected).norm(),
+                theta,
+            );
+        }
+    }
+
+    #[test]
+    fn test_ros_transform_round_trip() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f64>>::at_time(0);
+        let right_se3_at_0 = CoordinateSystem::<RightCameraSE3, Isometry3<f64>>::at_time(0);
+        let right_from_left = SE3Transform::new(
+            right_se3_at_0,
+            left_se3_at_0,
+            Isometry3::from_parts(
+                Translation3::new(-BASELINE as f64, 0.02, 0.),
+                UnitQuaternion::from_scaled_axis(Vector3::new(0.1, -0.2, 0.05)),
+            ),
+        );
+
+        let ros: RosTransform<f64> = right_from_left.into();
+        assert_eq!(ros.translation.x, -BASELINE as f64);
+        assert_eq!(ros.rotation.w, right_from_left.rotation().w);
+        assert_eq!(
+            serde_json::to_value(ros).unwrap()["rotation"]
+                .as_object()
+                .unwrap()
+                .len(),
+            4
+        );
+
+        let recovered = SE3Transform::from_ros(right_se3_at_0, left_se3_at_0, ros);
+        assert!(recovered.dst() == right_se3_at_0 && recovered.src() == left_se3_at_0);
+        assert!(
+            (recovered.isometry().to_homogeneous() - right_from_left.isometry().to_homogeneous())
+                .norm()
+                < 1e-12
+        );
+    }
+
+    #[test]
+    fn test_project_line() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f64>>::at_time(0);
+        let left_image_at_0 = CoordinateSystem::<LeftCameraImage, Vector2<f64>>::at_time(0);
+        let projection = ProjectiveTransform::from_intrinsics(
+            left_image_at_0,
+            left_se3_at_0,
+            LEFT_FOCAL_LEN as f64,
+            1.1 * LEFT_FOCAL_LEN as f64,
+            320.,
+            240.,
+        )
+        .with_skew(2.);
+        let point_at =
+            |x: f64, y: f64, z: f64| Point::new(left_se3_at_0, Isometry3::translation(x, y, z));
+        let a = point_at(0.1, -0.2, POINT_DISTANCE as f64);
+        let b = point_at(-0.3, 0.1, 2. * POINT_DISTANCE as f64);
+        let image_line = projection.project_line(Line3::from_points(a, b));
+        let k_inv = projection.k().try_inverse().unwrap();
+        let expected = k_inv.transpose() * Line3::from_points(a, b).moment();
+        assert!((image_line - expected).norm() < 1e-9 * expected.norm());
+
+        // Both endpoints, and any other Point on the 3-D line, project onto the image line.
+        let beyond_b = b.position() * 2. - a.position();
+        let c = point_at(beyond_b[0], beyond_b[1], beyond_b[2]);
+        for point in [a, b, c] {
+            let pixel = projection.transform(point).coordinates();
+            let residual = image_line.dot(&pixel.push(1.)) / image_line.xy().norm();
+            assert!(
+                residual.abs() < 1e-9,
+                "Pixel {:?} is {} off the line.",
+                pixel,
+                residual
+            );
+        }
+    }
+
+    #[test]
+    fn test_id_from_name() {
+        assert_eq!(LeftCameraSE3::name(), "LeftCameraSE3");
+        assert_eq!(id_from_name("LeftCameraSE3"), Some(LeftCameraSE3::name()));
+        assert_eq!(
+            id_from_name("RightCameraImage"),
+            Some(RightCameraImage::name())
+        );
+        assert_eq!(id_from_name("RearCameraSE3"), None);
+
+        let left_se3_at_3 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(3);
+        assert_eq!(
+            DynCoordinateSystem::from_name("LeftCameraSE3", 3),
+            Some(left_se3_at_3.into())
+        );
+        assert_eq!(DynCoordinateSystem::from_name("RearCameraSE3", 3), None);
+    }
+
+    #[test]
+    fn test_transform_ref() {
+        define_coordinate_system_id!(FeatureR64, RnRepr<64>);
+        define_coordinate_system_id!(ProjectedR8, RnRepr<8>);
+        let features_at_0 = CoordinateSystem::<FeatureR64, SVector<f32, 64>>::at_time(0);
+        let projected_at_0 = CoordinateSystem::<ProjectedR8, SVector<f32, 8>>::at_time(0);
+        let projection = LinearTransform::new(
+            projected_at_0,
+            features_at_0,
+            SMatrix::<f32, 8, 64>::from_fn(|i, j| (i as f32 - j as f32) * 0.01),
+        );
+        let point = Point::new(features_at_0, SVector::<f32, 64>::from_fn(|i, _| i as f32));
+        assert!(projection.transform_ref(&point) == projection.transform(point));
+
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        let right_se3_at_0 = CoordinateSystem::<RightCameraSE3, Isometry3<f32>>::at_time(0);
+        let right_from_left = SE3Transform::new(
+            right_se3_at_0,
+            left_se3_at_0,
+            Isometry3::translation(-BASELINE, 0., 0.),
+        );
+        let pose = Point::new(
+            left_se3_at_0,
+            Isometry3::translation(0., 0., POINT_DISTANCE),
+        );
+        assert!(right_from_left.transform_ref(&pose) == right_from_left.transform(pose));
+    }
+
+    #[test]
+    fn test_so3_se3_exp_log() {
+        // Deterministic pseudo-random vectors, scaled from small up to large angles.
+        let mut state = 12345u64;
+        let mut random = move || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 53) as f64 - 0.5
+        };
+        for i in 0..100 {
+            let scale = 10f64.powf(-6. + 0.06 * i as f64);
+            let omega = Vector3::new(random(), random(), random()) * scale;
+            assert!((so3::log(so3::exp(omega)) - omega).norm() < 1e-12);
+
+            let delta =
+                nalgebra::Vector6::new(omega[0], omega[1], omega[2], random(), random(), random());
+            assert!((se3::log(se3::exp(delta)) - delta).norm() < 1e-12);
+            let isometry = se3::exp(delta);
+            assert!(
+                (se3::exp(se3::log(isometry)).to_homogeneous() - isometry.to_homogeneous()).norm()
+                    < 1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn test_camera_rig_bytes() {
+        let left_intrinsics = left_intrinsics(12., -7.);
+        let right_intrinsics = right_intrinsics();
+        let se3_left_from_right =
+            se3_left_from_right(UnitQuaternion::from_euler_angles(0.01, 0.02, 0.03));
+        let rig = CameraRigBuilder::new()
+            .intrinsics(left_intrinsics)
+            .intrinsics(right_intrinsics)
+            .extrinsics(se3_left_from_right)
+            .build(ATOL)
+            .unwrap();
+
+        let bytes = rig.to_bytes().unwrap();
+        let reloaded = CameraRig::<f32>::from_bytes(&bytes).unwrap();
+        assert_eq!(reloaded.iter_sorted().count(), 2);
+        for ((dst, src, a), (reloaded_dst, reloaded_src, b)) in
+            rig.iter_sorted().zip(reloaded.iter_sorted())
+        {
+            assert_eq!((dst, src), (reloaded_dst, reloaded_src));
+            assert!((a.to_homogeneous() - b.to_homogeneous()).norm() < ATOL);
+        }
+        assert!(
+            reloaded
+                .intrinsics::<LeftCameraImage, LeftCameraSE3>()
+                .unwrap()
+                .k()
+                == left_intrinsics.k()
+        );
+        assert!(
+            reloaded
+                .intrinsics::<RightCameraImage, RightCameraSE3>()
+                .unwrap()
+                .k()
+                == right_intrinsics.k()
+        );
+
+        // Blobs of another version fail clearly, instead of being mis-parsed.
+        let mut old = bytes.clone();
+        old[4..8].copy_from_slice(&0u32.to_le_bytes());
+        assert_eq!(
+            CameraRig::<f32>::from_bytes(&old).unwrap_err(),
+            CalibrationError::UnsupportedVersion {
+                found: 0,
+                supported: CALIBRATION_VERSION,
+            }
+        );
+        assert_eq!(
+            CameraRig::<f32>::from_bytes(&bytes[..bytes.len() - 1]).unwrap_err(),
+            CalibrationError::Truncated
+        );
+        assert_eq!(
+            CameraRig::<f32>::from_bytes(b"nope").unwrap_err(),
+            CalibrationError::BadMagic
+        );
+
+        // Rigs with unregistered ids cannot be exported, since the blob could not be loaded again.
+        define_coordinate_system_id!(ImuSE3, SE3Repr);
+        let rig = CameraRigBuilder::new()
+            .extrinsics(StaticSE3Transform::<ImuSE3, LeftCameraSE3, f32>::new(
+                Isometry3::translation(0.2, 0., 0.),
+            ))
+            .build(ATOL)
+            .unwrap();
+        assert_eq!(
+            rig.to_bytes().unwrap_err(),
+            CalibrationError::UnknownId("ImuSE3".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_aim_at() {
+        let position = Vector3::new(1f32, 2., 3.);
+        let pose = Point::new(
+            CoordinateSystem::<LeftCameraSE3, _>::at_time(0),
+            Isometry3::from_parts(
+                Translation3::from(position),
+                UnitQuaternion::from_euler_angles(0.3, -0.2, 0.1),
+            ),
+        );
+        let target = Vector3::new(-4., 0., 10.);
+        let up = Vector3::new(0., 1., 0.);
+        let aimed = pose.aim_at(target, up);
+
+        assert!(aimed.coordinate_system() == pose.coordinate_system());
+        assert!((aimed.position() - position).norm() < ATOL);
+        let optical_axis = aimed.coordinates().rotation * Vector3::z();
+        assert!((optical_axis - (target - position).normalize()).norm() < ATOL);
+        // The new `+Y` axis is orthogonal to the optical axis, and as close to `up` as possible.
+        let y_axis = aimed.coordinates().rotation * Vector3::y();
+        assert!(y_axis.dot(&optical_axis).abs() < ATOL);
+        assert!(y_axis.dot(&up) > 0.);
+        assert!((y_axis.cross(&optical_axis).dot(&up)).abs() < ATOL);
+    }
+
+    #[test]
+    fn test_transform_point3() {
+        let isometry = Isometry3::from_parts(
+            Translation3::new(BASELINE, -0.2, 0.3),
+            UnitQuaternion::from_euler_angles(0.1, 0.2, -0.3),
+        );
+        let se3_left_from_right = SE3Transform::new(
+            CoordinateSystem::<LeftCameraSE3, _>::at_time(0),
+            CoordinateSystem::<RightCameraSE3, _>::at_time(0),
+            isometry,
+        );
+        let p = Vector3::new(1., 2., POINT_DISTANCE);
+        assert!(
+            (se3_left_from_right.transform_point3(p) - isometry.transform_point(&p.into()).coords)
+                .norm()
+                < ATOL
+        );
+    }
+
+    #[test]
+    fn test_reproject_depth() {
+        let left_projection = left_intrinsics(12., -7.).at_time(0);
+        let right_projection = right_intrinsics().at_time(0);
+        let se3_right_from_left =
+            se3_left_from_right(UnitQuaternion::from_euler_angles(0.01, -0.02, 0.))
+                .invert()
+                .at_time(0);
+
+        let left_unprojection = left_projection.invert();
+        let pixel = Vector2::new(30., -15.);
+        let warped = reproject_depth(
+            &left_unprojection,
+            &se3_right_from_left,
+            &right_projection,
+            pixel,
+            POINT_DISTANCE,
+        )
+        .unwrap();
+
+        let manual = right_projection.transform(
+            se3_right_from_left.transform(
+                left_projection
+                    .invert()
+                    .transform_with_depth(Point::new(left_projection.dst(), pixel), POINT_DISTANCE),
+            ),
+        );
+        assert!(warped.coordinate_system() == manual.coordinate_system());
+        assert!((warped.coordinates() - manual.coordinates()).norm() < ATOL);
+
+        // A Point which ends up behind the destination Camera has no pixel.
+        let se3_behind_from_left = StaticSE3Transform::<RightCameraSE3, LeftCameraSE3, _>::new(
+            Isometry3::translation(0., 0., -2. * POINT_DISTANCE),
+        )
+        .at_time(0);
+        assert!(reproject_depth(
+            &left_unprojection,
+            &se3_behind_from_left,
+            &right_projection,
+            pixel,
+            POINT_DISTANCE,
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_near() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        let pose = Isometry3::from_parts(
+            Translation3::new(0.1, 0.2, 0.3),
+            UnitQuaternion::from_scaled_axis(Vector3::new(0.1, 0.2, 0.3)),
+        );
+        let a = Point::new(left_se3_at_0, pose);
+        // The same rotation, written with the opposite quaternion sign.
+        let b = a.map_coordinates(|mut coordinates| {
+            coordinates.rotation =
+                UnitQuaternion::new_unchecked(-coordinates.rotation.into_inner());
+            coordinates
+        });
+        assert!(a.near(&b, ATOL));
+        assert!(!a.near(
+            &b.map_coordinates(|c| Isometry3::translation(0., 0., 0.01) * c),
+            ATOL
+        ));
+        assert!(!a.near(
+            &b.map_coordinates(|c| c * UnitQuaternion::from_euler_angles(0.01, 0., 0.)),
+            ATOL
+        ));
+        assert!(!a.near(&b.with_time(1), ATOL));
+
+        define_coordinate_system_id!(WorldR3, RnRepr<3>);
+        let world_at_0 = CoordinateSystem::<WorldR3, Vector3<f32>>::at_time(0);
+        let c = Point::new(world_at_0, Vector3::new(1., 2., 3.));
+        assert!(c.near(&c.map_coordinates(|c| c.add_scalar(ATOL / 10.)), ATOL));
+        assert!(!c.near(&c.map_coordinates(|c| c.add_scalar(ATOL * 10.)), ATOL));
+    }
+
+    #[test]
+    fn test_compose_with_dynamic() {
+        let se3_left_from_right =
+            se3_left_from_right(UnitQuaternion::from_euler_angles(0.01, 0.02, 0.03));
+        let se3_right_3_from_right_2 = SE3Transform::new(
+            CoordinateSystem::<RightCameraSE3, _>::at_time(3),
+            CoordinateSystem::<RightCameraSE3, _>::at_time(2),
+            Isometry3::from_parts(
+                Translation3::new(0.1, 0.2, 0.3),
+                UnitQuaternion::from_scaled_axis(Vector3::new(0.1, 0.2, 0.3)),
+            ),
+        );
+
+        let composed = se3_left_from_right.compose_with_dynamic(se3_right_3_from_right_2);
+        let two_step = se3_left_from_right
+            .at_time(3)
+            .compose_with(se3_right_3_from_right_2);
+        assert!(composed.src() == two_step.src());
+        assert_pose_near(as_pose(composed), as_pose(two_step), ATOL);
+    }
+
+    #[test]
+    fn test_motion() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        let left_se3_at_1 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(1);
+        let isometry = Isometry3::from_parts(
+            Translation3::new(0.1, 0.2, 0.3),
+            UnitQuaternion::from_scaled_axis(Vector3::new(0.1, 0.2, 0.3)),
+        );
+        let se3_left_1_from_left_0 =
+            SE3Transform::<LeftCameraSE3, LeftCameraSE3, _>::motion(0, 1, isometry);
+        assert!(se3_left_1_from_left_0.src() == left_se3_at_0);
+        assert!(se3_left_1_from_left_0.dst() == left_se3_at_1);
+
+        let point = Point::new(
+            left_se3_at_0,
+            Isometry3::translation(0., 0., POINT_DISTANCE),
+        );
+        let moved = se3_left_1_from_left_0.transform(point);
+        assert!(moved.coordinate_system() == left_se3_at_1);
+        assert!(moved.near(
+            &Point::new(left_se3_at_1, isometry * point.coordinates()),
+            ATOL
+        ));
+    }
+
+    #[test]
+    fn test_relative_to() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        let robot = Point::new(left_se3_at_0, sample_pose());
+        let landmark = Point::new(
+            left_se3_at_0,
+            Isometry3::translation(0., 0., POINT_DISTANCE),
+        );
+
+        assert!(robot
+            .relative_to(robot)
+            .near(&Point::new(left_se3_at_0, Isometry3::identity()), ATOL));
+        let relative = landmark.relative_to(robot);
+        assert!(relative.coordinate_system() == left_se3_at_0);
+        assert!(
+            Point::new(left_se3_at_0, robot.coordinates() * relative.coordinates())
+                .near(&landmark, ATOL)
+        );
+    }
+
+    #[test]
+    fn test_pow() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        let pose = Point::new(left_se3_at_0, sample_pose());
+
+        let half = pose.pow(0.5);
+        assert!(half.coordinate_system() == left_se3_at_0);
+        assert!(
+            Point::new(left_se3_at_0, half.coordinates() * half.coordinates()).near(&pose, ATOL)
+        );
+        assert!(pose
+            .pow(0.)
+            .near(&Point::new(left_se3_at_0, Isometry3::identity()), ATOL));
+        assert!(pose.pow(-1.).near(
+            &Point::new(left_se3_at_0, pose.coordinates().inverse()),
+            ATOL
+        ));
+    }
+
+    #[test]
+    fn test_group_mul_batch() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        let left_se3_at_1 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(1);
+        let absolute = Point::new(left_se3_at_0, sample_pose());
+        let relatives: Vec<_> = (0..4)
+            .map(|i| {
+                Point::new(
+                    left_se3_at_0,
+                    Isometry3::new(
+                        Vector3::new(i as f32, 0., POINT_DISTANCE),
+                        Vector3::new(0., 0.1 * i as f32, 0.),
+                    ),
+                )
+            })
+            .collect();
+
+        let batch = absolute.group_mul_batch(&relatives);
+        assert_eq!(batch.len(), relatives.len());
+        for (product, relative) in batch.iter().zip(&relatives) {
+            assert!(product.near(&absolute.group_mul(*relative), ATOL));
+        }
+        assert!(absolute.group_mul_batch(&[]).is_empty());
+
+        let wrong_frame = [Point::new(left_se3_at_1, Isometry3::identity())];
+        let result = std::panic::catch_unwind(|| absolute.group_mul_batch(&wrong_frame));
+        assert_eq!(result.is_err(), cfg!(debug_assertions));
+    }
+
+    #[test]
+    fn test_se3_transform_lerp_decoupled() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        let right_se3_at_0 = CoordinateSystem::<RightCameraSE3, Isometry3<f32>>::at_time(0);
+        let motion = |isometry| {
+            SE3Transform::new(
+                right_se3_at_0.with_time(10),
+                left_se3_at_0.with_time(10),
+                isometry,
+            )
+        };
+        let start = SE3Transform::new(right_se3_at_0, left_se3_at_0, Isometry3::identity());
+        let rotation = UnitQuaternion::from_scaled_axis(Vector3::new(0., 0.4, 0.));
+
+        // For a pure rotation, the screw motion and the decoupled interpolation agree.
+        let rotated = motion(Isometry3::from_parts(Translation3::identity(), rotation));
+        let decoupled = start.lerp_decoupled(&rotated, 0.5);
+        assert!(decoupled.src() == left_se3_at_0.with_time(5));
+        assert_pose_near(
+            as_pose(decoupled),
+            as_pose(start.lerp_to(&rotated, 0.5)),
+            ATOL,
+        );
+
+        // With a translation too, the decoupled translation moves along a straight line,
+        // while the screw motion bends it.
+        let moved = motion(Isometry3::from_parts(
+            Translation3::new(1., 0., 0.),
+            rotation,
+        ));
+        let decoupled = start.lerp_decoupled(&moved, 0.5);
+        let screw = start.lerp_to(&moved, 0.5);
+        assert!(
+            (decoupled.isometry().translation.vector - Vector3::new(0.5, 0., 0.)).norm() < ATOL
+        );
+        assert!(
+            decoupled
+                .isometry()
+                .rotation
+                .angle_to(&screw.isometry().rotation)
+                < ATOL
+        );
+        assert!(
+            (decoupled.isometry().translation.vector - screw.isometry().translation.vector).norm()
+                > 1e-3
+        );
+    }
+
+    #[test]
+    fn test_point_identity() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        let identity = Point::identity(left_se3_at_0);
+        assert!(identity.coordinate_system() == left_se3_at_0);
+        assert_eq!(identity.position(), Vector3::zeros());
+        assert_eq!(identity.coordinates().rotation, UnitQuaternion::identity());
+
+        let pose = Point::new(left_se3_at_0, sample_pose());
+        assert!(pose.group_mul(identity) == pose);
+        assert!(identity.group_mul(pose) == pose);
+    }
+
+    #[test]
+    fn test_blend() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        let pose = Point::new(left_se3_at_0, sample_pose());
+        assert!(Point::<LeftCameraSE3, Isometry3<f32>>::blend(&[]).is_none());
+        assert!(Point::blend(&[(pose, 0.3)]) == Some(pose));
+        assert!(Point::blend(&[(pose, 0.2), (pose, 1.), (pose, 3.)])
+            .unwrap()
+            .near(&pose, ATOL));
+
+        // Two equally weighted poses blend to the midpoint of the geodesic between them.
+        let other = Point::new(
+            left_se3_at_0,
+            Isometry3::new(Vector3::new(0.3, -0.1, 0.2), Vector3::new(0.2, 0.1, -0.3)),
+        );
+        let midpoint = Point::new(
+            left_se3_at_0,
+            pose.coordinates() * other.relative_to(pose).pow(0.5).coordinates(),
+        );
+        assert_pose_near(
+            Point::blend(&[(pose, 2.), (other, 2.)]).unwrap(),
+            midpoint,
+            1e-5,
+        );
+    }
+
+    #[test]
+    fn test_step_toward() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f32>>::at_time(0);
+        let start = Point::identity(left_se3_at_0);
+        let target = Point::new(left_se3_at_0, sample_pose());
+        let distance = se3::log(target.coordinates()).norm();
+
+        // Within one step, the target is reached.
+        assert!(start.step_toward(target, distance + 0.1) == target);
+
+        // Otherwise, the step is clamped, along the geodesic toward the target.
+        let max_step = distance / 4.;
+        let stepped = start.step_toward(target, max_step);
+        assert!((se3::log(stepped.coordinates()).norm() - max_step).abs() < 1e-5);
+        assert_pose_near(stepped, target.pow(0.25), 1e-5);
+        let mut follower = start;
+        for _ in 0..4 {
+            let next = follower.step_toward(target, max_step);
+            assert!(
+                se3::log(follower.coordinates().inverse() * next.coordinates()).norm()
+                    <= max_step + 1e-5
+            );
+            follower = next;
+        }
+        assert_pose_near(follower, target, 1e-5);
+    }
+
+    #[test]
+    fn test_point_ord() {
+        let left_se3_at_0 = CoordinateSystem::<LeftCameraSE3, Isometry3<f64>>::at_time(0);
+        let poses: Vec<_> = (0..5)
+            .map(|i| {
+                Point::new(
+                    left_se3_at_0.with_time(i % 2),
+                    Isometry3::new(
+                        Vector3::new(i as f64, -0.5 * i as f64, 1.),
+                        Vector3::new(0.1 * i as f64, 0., 0.2),
+                    ),
+                )
+            })
+            .collect();
+        let cache: std::collections::BTreeMap<_, _> = poses
+            .iter()
+            .enumerate()
+            .map(|(i, pose)| (*pose, i))
+            .collect();
+        assert_eq!(cache.len(), poses.len());
+        for (i, pose) in poses.iter().enumerate() {
+            assert_eq!(cache.get(pose), Some(&i));
+            assert_eq!(pose.cmp(pose), std::cmp::Ordering::Equal);
+        }
+        // Earlier times come first, and the order agrees with `Eq`.
+        assert!(poses[0] < poses[1] && poses[2] < poses[1]);
+        assert!(cache
+            .keys()
+            .zip(cache.keys().skip(1))
+            .all(|(a, b)| a < b && a != b));
+        let moved = poses[0].map_coordinates(|c| Isometry3::translation(0., 0., 1e-12) * c);
+        assert!(!cache.contains_key(&moved));
+    }
 }
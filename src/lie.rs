@@ -0,0 +1,67 @@
+//! Provides the exponential and logarithm maps of SO3 and SE3 relative to identity, as free functions
+//! on plain nalgebra types.
+
+/// Exponential and logarithm maps of SO3, for rotation vectors `ω` (axis times angle).
+pub mod so3 {
+    use nalgebra::{RealField, UnitQuaternion, Vector3};
+
+    pub fn exp<T: Copy + RealField>(omega: Vector3<T>) -> UnitQuaternion<T> {
+        UnitQuaternion::from_scaled_axis(omega)
+    }
+
+    /// Inverse of [`exp`], with the angle in `[0, π]`.
+    pub fn log<T: Copy + RealField>(rotation: UnitQuaternion<T>) -> Vector3<T> {
+        rotation.scaled_axis()
+    }
+}
+
+/// Exponential and logarithm maps of SE3, for twists `δ = [ω; v]` (rotation first).
+pub mod se3 {
+    use nalgebra::{Isometry3, Matrix3, RealField, Translation3, Vector3, Vector6};
+
+    use super::so3;
+
+    pub fn exp<T: Copy + RealField>(delta: Vector6<T>) -> Isometry3<T> {
+        let omega: Vector3<T> = delta.fixed_rows::<3>(0).into_owned();
+        let v: Vector3<T> = delta.fixed_rows::<3>(3).into_owned();
+        let theta = omega.norm();
+        let w = omega.cross_matrix();
+        // Left Jacobian of SO3. The coefficients `(1 - cos θ) / θ²` and `(θ - sin θ) / θ³` lose precision to
+        // cancellation for small θ, so below `epsilon^(1/4)` use their Taylor series instead. The truncation
+        // error there is `O(θ⁴)`, i.e. `O(epsilon)`, so both branches agree to rounding at the threshold.
+        let theta_sq = theta * theta;
+        let (a, b) = if theta < T::default_epsilon().sqrt().sqrt() {
+            (
+                nalgebra::convert::<f64, T>(0.5) - theta_sq / nalgebra::convert(24.),
+                nalgebra::convert::<f64, T>(1. / 6.) - theta_sq / nalgebra::convert(120.),
+            )
+        } else {
+            (
+                (T::one() - theta.cos()) / theta_sq,
+                (theta - theta.sin()) / (theta_sq * theta),
+            )
+        };
+        let left_jacobian = Matrix3::identity() + w * a + w * w * b;
+        Isometry3::from_parts(Translation3::from(left_jacobian * v), so3::exp(omega))
+    }
+
+    /// Inverse of [`exp`], with the rotation angle in `[0, π]`.
+    pub fn log<T: Copy + RealField>(isometry: Isometry3<T>) -> Vector6<T> {
+        let omega = so3::log(isometry.rotation);
+        let theta = omega.norm();
+        let w = omega.cross_matrix();
+        // Inverse of the left Jacobian of SO3, with the same Taylor-series treatment as in `exp`.
+        let theta_sq = theta * theta;
+        let c = if theta < T::default_epsilon().sqrt().sqrt() {
+            nalgebra::convert::<f64, T>(1. / 12.) + theta_sq / nalgebra::convert(720.)
+        } else {
+            // `θ sin θ / (2 (1 - cos θ)) == (θ / 2) cot(θ / 2)`, which avoids computing `1 - cos θ`.
+            let half_theta = theta / nalgebra::convert(2.);
+            (T::one() - half_theta * half_theta.cos() / half_theta.sin()) / theta_sq
+        };
+        let inv_left_jacobian =
+            Matrix3::identity() - w * nalgebra::convert::<f64, T>(0.5) + w * w * c;
+        let v = inv_left_jacobian * isometry.translation.vector;
+        Vector6::new(omega[0], omega[1], omega[2], v[0], v[1], v[2])
+    }
+}
@@ -0,0 +1,60 @@
+//! Provides 3-D lines in Plücker coordinates, written relative to an SE3 [`CoordinateSystem`].
+
+use std::fmt::Debug;
+
+use nalgebra::{Isometry3, RealField, Vector3};
+use serde::Serialize;
+
+use crate::{CoordinateSystem, IsCoordinateSystemId, Point};
+
+/// An infinite 3-D line in Plücker coordinates: a `direction` `d`, and a `moment` `m = p × d`
+/// for any position `p` on the line.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Line3<Id, T>
+where
+    Id: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    coordinate_system: CoordinateSystem<Id, Isometry3<T>>,
+    direction: Vector3<T>,
+    moment: Vector3<T>,
+}
+
+impl<Id, T> Line3<Id, T>
+where
+    Id: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    /// The line through the positions of two distinct [`Point`]s in the same [`CoordinateSystem`].
+    pub fn from_points(a: Point<Id, Isometry3<T>>, b: Point<Id, Isometry3<T>>) -> Self {
+        assert!(
+            a.coordinate_system() == b.coordinate_system(),
+            "Coordinate system {:?} does not match coordinate system {:?}.",
+            a.coordinate_system(),
+            b.coordinate_system(),
+        );
+        let direction = b.position() - a.position();
+        assert!(
+            direction != Vector3::zeros(),
+            "Points must be distinct to define a line, got {:?} twice.",
+            a.position(),
+        );
+        Self {
+            coordinate_system: a.coordinate_system(),
+            direction,
+            moment: a.position().cross(&direction),
+        }
+    }
+
+    pub fn coordinate_system(&self) -> CoordinateSystem<Id, Isometry3<T>> {
+        self.coordinate_system
+    }
+
+    pub fn direction(&self) -> Vector3<T> {
+        self.direction
+    }
+
+    pub fn moment(&self) -> Vector3<T> {
+        self.moment
+    }
+}
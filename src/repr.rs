@@ -0,0 +1,86 @@
+//! Ties each [`IsCoordinateSystemId`](crate::IsCoordinateSystemId) to the Representations (`Repr`s)
+//! that its [`CoordinateSystem`](crate::CoordinateSystem)s may use.
+//!
+//! For example, an SE3 id such as "LeftCameraSE3" may only be used with [`nalgebra::Isometry3`]
+//! coordinates, and an Image id such as "LeftCameraImage" only with [`nalgebra::Vector2`] coordinates.
+
+use nalgebra::{Isometry3, RealField, SVector, Vector2};
+
+use crate::HomogeneousPixel;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Marker Trait for kinds of Representation. Each [`IsCoordinateSystemId`](crate::IsCoordinateSystemId) has one.
+pub trait IsReprKind {}
+
+/// Marker Trait for a `Repr` being a valid Representation of the kind `Kind`.
+///
+/// This Trait is sealed: the valid Representations are fixed by this crate.
+pub trait IsReprOf<Kind: IsReprKind>: sealed::Sealed {}
+
+/// 3-D Poses, represented by [`nalgebra::Isometry3`].
+#[derive(Debug, Clone, Copy)]
+pub struct SE3Repr;
+impl IsReprKind for SE3Repr {}
+
+/// Image-Plane pixels, represented by [`nalgebra::Vector2`], or by [`HomogeneousPixel`] in homogeneous coordinates.
+#[derive(Debug, Clone, Copy)]
+pub struct ImageRepr;
+impl IsReprKind for ImageRepr {}
+
+/// N-dimensional vectors, represented by [`nalgebra::SVector`].
+#[derive(Debug, Clone, Copy)]
+pub struct RnRepr<const N: usize>;
+impl<const N: usize> IsReprKind for RnRepr<N> {}
+
+impl<T: RealField> sealed::Sealed for Isometry3<T> {}
+impl<T: RealField> IsReprOf<SE3Repr> for Isometry3<T> {}
+
+impl<T: RealField, const N: usize> sealed::Sealed for SVector<T, N> {}
+impl<T: RealField> IsReprOf<ImageRepr> for Vector2<T> {}
+
+impl<T: RealField + Copy> sealed::Sealed for HomogeneousPixel<T> {}
+impl<T: RealField + Copy> IsReprOf<ImageRepr> for HomogeneousPixel<T> {}
+impl<T: RealField, const N: usize> IsReprOf<RnRepr<N>> for SVector<T, N> {}
+
+/// Scalars which can be compared and hashed exactly by their bit patterns, i.e. `f32` and `f64`.
+pub trait IsBitScalar: Copy {
+    fn to_bits_u64(self) -> u64;
+}
+
+impl IsBitScalar for f32 {
+    fn to_bits_u64(self) -> u64 {
+        self.to_bits() as u64
+    }
+}
+
+impl IsBitScalar for f64 {
+    fn to_bits_u64(self) -> u64 {
+        self.to_bits()
+    }
+}
+
+/// `Repr`s which can be compared and hashed exactly by the bit patterns of their scalars,
+/// which gives [`crate::Point`]s `Eq` and `Hash`.
+pub trait IsBitHashable {
+    /// Bit patterns of the scalars, in a fixed order.
+    fn bits(&self) -> impl Iterator<Item = u64> + '_;
+}
+
+impl<T: RealField + IsBitScalar, const N: usize> IsBitHashable for SVector<T, N> {
+    fn bits(&self) -> impl Iterator<Item = u64> + '_ {
+        self.iter().map(|x| x.to_bits_u64())
+    }
+}
+
+impl<T: RealField + IsBitScalar> IsBitHashable for Isometry3<T> {
+    fn bits(&self) -> impl Iterator<Item = u64> + '_ {
+        self.translation
+            .vector
+            .iter()
+            .chain(self.rotation.coords.iter())
+            .map(|x| x.to_bits_u64())
+    }
+}
@@ -0,0 +1,87 @@
+//! Provides plain structs with the layout of ROS `geometry_msgs/Transform`, for bridging to ROS.
+//!
+//! ROS carries the frame ids as strings in the message header, so they are dropped when converting
+//! to a [`RosTransform`], and must be supplied when converting back.
+
+use nalgebra::{Isometry3, Quaternion, RealField, Translation3, UnitQuaternion};
+use serde::{Deserialize, Serialize};
+
+use crate::{CoordinateSystem, IsCoordinateSystemId, SE3Transform};
+
+/// Layout of ROS `geometry_msgs/Vector3`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RosVector3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// Layout of ROS `geometry_msgs/Quaternion`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RosQuaternion<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+    pub w: T,
+}
+
+/// Layout of ROS `geometry_msgs/Transform`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RosTransform<T> {
+    pub translation: RosVector3<T>,
+    pub rotation: RosQuaternion<T>,
+}
+
+impl<DstId, SrcId, T> From<SE3Transform<DstId, SrcId, T>> for RosTransform<T>
+where
+    DstId: IsCoordinateSystemId,
+    SrcId: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    fn from(transform: SE3Transform<DstId, SrcId, T>) -> Self {
+        let t = transform.isometry().translation.vector;
+        let q = transform.rotation().coords;
+        Self {
+            translation: RosVector3 {
+                x: t[0],
+                y: t[1],
+                z: t[2],
+            },
+            rotation: RosQuaternion {
+                x: q[0],
+                y: q[1],
+                z: q[2],
+                w: q[3],
+            },
+        }
+    }
+}
+
+impl<DstId, SrcId, T> SE3Transform<DstId, SrcId, T>
+where
+    DstId: IsCoordinateSystemId,
+    SrcId: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    /// Build an [`SE3Transform`] from a [`RosTransform`], supplying its [`CoordinateSystem`]s.
+    ///
+    /// The rotation is normalized, since ROS messages do not guarantee a unit quaternion.
+    pub fn from_ros(
+        dst: CoordinateSystem<DstId, Isometry3<T>>,
+        src: CoordinateSystem<SrcId, Isometry3<T>>,
+        ros: RosTransform<T>,
+    ) -> Self {
+        let RosTransform {
+            translation: t,
+            rotation: q,
+        } = ros;
+        SE3Transform::new(
+            dst,
+            src,
+            Isometry3::from_parts(
+                Translation3::new(t.x, t.y, t.z),
+                UnitQuaternion::from_quaternion(Quaternion::new(q.w, q.x, q.y, q.z)),
+            ),
+        )
+    }
+}
@@ -0,0 +1,39 @@
+//! Serialization of [`Isometry3`] coordinates which depends on the format.
+//!
+//! Human-readable formats (e.g. JSON config files) get a flat `[tx, ty, tz, qx, qy, qz, qw]` array.
+//! Compact formats (e.g. bincode) get nalgebra's default representation.
+//!
+//! Use with `#[serde(with = "geometry::serde_isometry")]` on an [`Isometry3`] field.
+
+use nalgebra::{Isometry3, Quaternion, RealField, Translation3, UnitQuaternion};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub fn serialize<T, S>(isometry: &Isometry3<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Copy + RealField + Serialize,
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        let t = isometry.translation.vector;
+        let q = isometry.rotation.coords;
+        [t[0], t[1], t[2], q[0], q[1], q[2], q[3]].serialize(serializer)
+    } else {
+        isometry.serialize(serializer)
+    }
+}
+
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Isometry3<T>, D::Error>
+where
+    T: Copy + RealField + Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        let [tx, ty, tz, qx, qy, qz, qw] = <[T; 7]>::deserialize(deserializer)?;
+        Ok(Isometry3::from_parts(
+            Translation3::new(tx, ty, tz),
+            UnitQuaternion::from_quaternion(Quaternion::new(qw, qx, qy, qz)),
+        ))
+    } else {
+        Isometry3::deserialize(deserializer)
+    }
+}
@@ -1,12 +1,18 @@
 //! Provides utilities for **static** transforms between [`CoordinateSystem`]s
 //! that do not change with time.
 
-use std::{fmt::Debug, marker::PhantomData};
+use std::{
+    fmt::{self, Debug, Display},
+    marker::PhantomData,
+};
 
-use nalgebra::{Isometry3, Matrix3, RealField};
+use nalgebra::{Isometry3, Matrix3, RealField, Vector2};
 use serde::Serialize;
 
-use crate::{CoordinateSystem, IsCoordinateSystemId, ProjectiveTransform, SE3Transform};
+use crate::{
+    transform::assert_same_handedness, CoordinateSystem, IsCoordinateSystemId, IsReprOf,
+    IsTransform, ProjectiveTransform, SE3Transform,
+};
 
 /// Static version of [`SE3Transform`] that does not change with time.
 #[derive(Debug, Clone, Copy, Serialize)]
@@ -29,6 +35,7 @@ where
 {
     pub fn new(transform: Isometry3<T>) -> Self {
         // TODO: figure out some way to prevent setting identity transform
+        assert_same_handedness::<DstId, SrcId>();
         Self {
             _src: PhantomData,
             _dst: PhantomData,
@@ -40,7 +47,10 @@ where
         self.transform
     }
 
-    pub fn at_time(&self, time: u64) -> SE3Transform<DstId, SrcId, T> {
+    pub fn at_time(&self, time: u64) -> SE3Transform<DstId, SrcId, T>
+    where
+        Isometry3<T>: IsReprOf<DstId::Kind> + IsReprOf<SrcId::Kind>,
+    {
         SE3Transform::new(
             CoordinateSystem::at_time(time),
             CoordinateSystem::at_time(time),
@@ -61,6 +71,19 @@ where
     {
         StaticSE3Transform::new(self.transform * rhs.transform)
     }
+
+    /// Compose with a time-varying [`SE3Transform`], by materializing `self` at the time of the
+    /// `dst` [`CoordinateSystem`] of `rhs` (see [`StaticSE3Transform::at_time`]).
+    pub fn compose_with_dynamic<RhsSrcId>(
+        &self,
+        rhs: SE3Transform<SrcId, RhsSrcId, T>,
+    ) -> SE3Transform<DstId, RhsSrcId, T>
+    where
+        RhsSrcId: IsCoordinateSystemId,
+        Isometry3<T>: IsReprOf<DstId::Kind> + IsReprOf<SrcId::Kind>,
+    {
+        self.at_time(rhs.dst().time()).compose_with(rhs)
+    }
 }
 
 /// Static version of [`ProjectiveTransform`] that does not change with time.
@@ -95,7 +118,11 @@ where
         self.k
     }
 
-    pub fn at_time(&self, time: u64) -> ProjectiveTransform<DstId, SrcId, T> {
+    pub fn at_time(&self, time: u64) -> ProjectiveTransform<DstId, SrcId, T>
+    where
+        Vector2<T>: IsReprOf<DstId::Kind>,
+        Isometry3<T>: IsReprOf<SrcId::Kind>,
+    {
         ProjectiveTransform::new(
             CoordinateSystem::at_time(time),
             CoordinateSystem::at_time(time),
@@ -103,3 +130,82 @@ where
         )
     }
 }
+
+/// Error returned when a [`TimedTransform`] is requested outside of its validity interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfRangeError {
+    pub time: u64,
+    pub valid_range: (u64, u64),
+}
+
+impl Display for OutOfRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Time {} is outside of the valid range [{}, {}].",
+            self.time, self.valid_range.0, self.valid_range.1,
+        )
+    }
+}
+
+impl std::error::Error for OutOfRangeError {}
+
+/// A [`StaticSE3Transform`] that is only valid over the closed time interval `valid_range`,
+/// e.g. an extrinsic calibration between two re-calibrations.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct TimedTransform<DstId, SrcId, T>
+where
+    DstId: IsCoordinateSystemId,
+    SrcId: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    transform: StaticSE3Transform<DstId, SrcId, T>,
+    valid_range: (u64, u64),
+}
+
+impl<DstId, SrcId, T> TimedTransform<DstId, SrcId, T>
+where
+    T: Copy + RealField + Serialize,
+    DstId: IsCoordinateSystemId,
+    SrcId: IsCoordinateSystemId,
+{
+    pub fn new(transform: StaticSE3Transform<DstId, SrcId, T>, valid_range: (u64, u64)) -> Self {
+        assert!(
+            valid_range.0 <= valid_range.1,
+            "Start of valid range {} is after its end {}.",
+            valid_range.0,
+            valid_range.1,
+        );
+        Self {
+            transform,
+            valid_range,
+        }
+    }
+
+    pub fn transform(&self) -> StaticSE3Transform<DstId, SrcId, T> {
+        self.transform
+    }
+
+    pub fn valid_range(&self) -> (u64, u64) {
+        self.valid_range
+    }
+
+    /// Whether the Transform is valid at `time`.
+    pub fn contains(&self, time: u64) -> bool {
+        self.valid_range.0 <= time && time <= self.valid_range.1
+    }
+
+    /// Get the [`SE3Transform`] at `time`, or an error if `time` is outside of the valid range.
+    pub fn at_time(&self, time: u64) -> Result<SE3Transform<DstId, SrcId, T>, OutOfRangeError>
+    where
+        Isometry3<T>: IsReprOf<DstId::Kind> + IsReprOf<SrcId::Kind>,
+    {
+        if !self.contains(time) {
+            return Err(OutOfRangeError {
+                time,
+                valid_range: self.valid_range,
+            });
+        }
+        Ok(self.transform.at_time(time))
+    }
+}
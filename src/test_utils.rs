@@ -0,0 +1,88 @@
+//! Assertion helpers for comparing [`Point`]s and Jacobians in tests, enabled by the `test-utils` feature.
+
+use nalgebra::{Isometry3, Matrix2x3, RealField, UnitQuaternion, Vector2, Vector3};
+use serde::Serialize;
+
+use crate::{IsCoordinateSystemId, Point};
+
+/// Assert that two rotations differ by an angle of at most `atol`.
+pub fn assert_rotation_near<T>(a: UnitQuaternion<T>, b: UnitQuaternion<T>, atol: T)
+where
+    T: Copy + RealField,
+{
+    let angle = a.angle_to(&b);
+    assert!(
+        angle <= atol,
+        "Rotations {:?} and {:?} differ by angle {}, which exceeds tolerance {}.",
+        a,
+        b,
+        angle,
+        atol,
+    );
+}
+
+/// Assert that two SE3 [`Point`]s are in the same [`crate::CoordinateSystem`], and that their
+/// translations and rotations each differ by at most `atol`.
+pub fn assert_pose_near<Id, T>(a: Point<Id, Isometry3<T>>, b: Point<Id, Isometry3<T>>, atol: T)
+where
+    Id: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    assert!(
+        a.coordinate_system() == b.coordinate_system(),
+        "Coordinate system {:?} does not match coordinate system {:?}.",
+        a.coordinate_system(),
+        b.coordinate_system(),
+    );
+    let distance = (a.coordinates().translation.vector - b.coordinates().translation.vector).norm();
+    assert!(
+        distance <= atol,
+        "Translations {:?} and {:?} differ by {}, which exceeds tolerance {}.",
+        a.coordinates().translation,
+        b.coordinates().translation,
+        distance,
+        atol,
+    );
+    assert_rotation_near(a.coordinates().rotation, b.coordinates().rotation, atol);
+}
+
+/// Estimate the Jacobian of `f` at `x` by central finite differences.
+///
+/// The step along each axis is `cbrt(epsilon) * max(1, |x_i|)`, which balances truncation and rounding error.
+pub fn finite_difference_jacobian<T, F>(f: F, x: Vector3<T>) -> Matrix2x3<T>
+where
+    T: Copy + RealField,
+    F: Fn(Vector3<T>) -> Vector2<T>,
+{
+    let cbrt_eps = T::default_epsilon().powf(nalgebra::convert(1. / 3.));
+    let two: T = nalgebra::convert(2.);
+    let mut jacobian = Matrix2x3::zeros();
+    for i in 0..3 {
+        let step = cbrt_eps * T::max(T::one(), x[i].abs());
+        let mut forward = x;
+        forward[i] += step;
+        let mut backward = x;
+        backward[i] -= step;
+        jacobian.set_column(i, &((f(forward) - f(backward)) / (two * step)));
+    }
+    jacobian
+}
+
+/// Assert that the `analytic` Jacobian of `f` at `x` matches its finite-difference estimate,
+/// with each entry differing by at most `atol`.
+pub fn assert_jacobian_matches<T, F>(analytic: Matrix2x3<T>, f: F, x: Vector3<T>, atol: T)
+where
+    T: Copy + RealField,
+    F: Fn(Vector3<T>) -> Vector2<T>,
+{
+    let numeric = finite_difference_jacobian(f, x);
+    let error = (analytic - numeric).abs().max();
+    assert!(
+        error <= atol,
+        "Analytic Jacobian {:?} and finite-difference Jacobian {:?} differ by {}, which exceeds tolerance {}.",
+        analytic,
+        numeric,
+        error,
+        atol,
+    );
+}
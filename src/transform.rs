@@ -1,11 +1,20 @@
 //! Provides the framework for transforming [`Point`]s between different [`CoordinateSystem`]s.
 
-use std::fmt::Debug;
+use std::{
+    fmt::{self, Debug, Display},
+    ops::Mul,
+};
 
-use nalgebra::{Isometry3, Matrix3, RealField, Vector2};
+use nalgebra::{
+    Isometry3, Matrix2x3, Matrix2x6, Matrix3, Matrix3x6, Matrix6, RealField, Rotation3, SMatrix,
+    SVector, Translation3, UnitQuaternion, Vector2, Vector3, Vector6,
+};
 use serde::Serialize;
 
-use super::{CoordinateSystem, IsCoordinateSystemId, Point};
+use super::{
+    se3, CoordinateSystem, DynCoordinateSystem, HomogeneousPixel, InverseDepthPoint,
+    IsCoordinateSystemId, IsReprOf, Line3, Point,
+};
 
 /// Trait for Transforms between [`CoordinateSystem`]s.
 pub trait IsTransform<DstId, DstRepr, SrcId, SrcRepr>: Debug + Copy + Serialize
@@ -24,18 +33,127 @@ where
     ///
     /// This function should not be implemented; it merely peforms a check and then
     /// calls `transform_inner`. Instead implement `transform_inner`.
+    ///
+    /// The check is a `debug_assert!`: release builds trust the caller to pass a [`Point`] in the
+    /// `src` [`CoordinateSystem`]. Use `try_transform` where the check must always happen.
+    ///
+    /// The same policy holds across the crate: checks on each [`Point`] (or pixel, or Line) are
+    /// debug-only, while checks made when building or composing Transforms always run.
     fn transform(&self, point: Point<SrcId, SrcRepr>) -> Point<DstId, DstRepr> {
-        assert!(
-            self.src() == point.coordinate_system(),
-            "Transform source coordinate system {:?} does not match Point coordinate system {:?}.",
-            self.src(),
-            point.coordinate_system(),
-        );
+        debug_assert_src(self.src(), point.coordinate_system());
         self.transform_inner(point)
     }
+    /// Version of `transform` which takes the [`Point`] by reference.
+    ///
+    /// The default implementation copies the [`Point`] after the check. [`LinearTransform`] and
+    /// [`SE3Transform`] override it to read the `coordinates` through the reference instead, so
+    /// large Representations are not copied; other implementors with large Representations should too.
+    fn transform_ref(&self, point: &Point<SrcId, SrcRepr>) -> Point<DstId, DstRepr> {
+        debug_assert_src(self.src(), point.coordinate_system());
+        self.transform_inner(*point)
+    }
+    /// Always-checked version of `transform`, which returns an error instead of panicking
+    /// if the [`Point`] is not in the `src` [`CoordinateSystem`], in both debug and release builds.
+    fn try_transform(
+        &self,
+        point: Point<SrcId, SrcRepr>,
+    ) -> Result<Point<DstId, DstRepr>, CoordinateSystemMismatch> {
+        if self.src() != point.coordinate_system() {
+            return Err(CoordinateSystemMismatch {
+                expected: self.src().into(),
+                found: point.coordinate_system().into(),
+            });
+        }
+        Ok(self.transform_inner(point))
+    }
     /// Performs the Transform after performing a run-time check.
     /// Should not be called by an external user, instead call `transform`.
     fn transform_inner(&self, point: Point<SrcId, SrcRepr>) -> Point<DstId, DstRepr>;
+    /// Lazily applies the Transform to a stream of [`Point`]s.
+    ///
+    /// The [`CoordinateSystem`] of the first [`Point`] is always checked, even in release builds,
+    /// and those of the rest only in debug builds, as in `transform`.
+    fn transform_iter<I>(&self, points: I) -> impl Iterator<Item = Point<DstId, DstRepr>>
+    where
+        I: IntoIterator<Item = Point<SrcId, SrcRepr>>,
+    {
+        let transform = *self;
+        points.into_iter().enumerate().map(move |(i, point)| {
+            if i == 0 {
+                assert_src(transform.src(), point.coordinate_system());
+            } else {
+                debug_assert_src(transform.src(), point.coordinate_system());
+            }
+            transform.transform_inner(point)
+        })
+    }
+}
+
+/// Check that `found` (of a [`Point`], or of an adjacent Transform) is the `src` [`CoordinateSystem`]
+/// of a Transform, in both debug and release builds.
+#[track_caller]
+pub(crate) fn assert_src<Id, Repr>(
+    src: CoordinateSystem<Id, Repr>,
+    found: CoordinateSystem<Id, Repr>,
+) where
+    Id: IsCoordinateSystemId,
+    Repr: Debug + Copy + Serialize,
+{
+    assert!(
+        src == found,
+        "Transform source coordinate system {:?} does not match coordinate system {:?} ({}).",
+        src,
+        found,
+        DynCoordinateSystem::from(src).diff(&found.into()),
+    );
+}
+
+/// Check, in debug builds only, that `found` is the `src` [`CoordinateSystem`] of a Transform.
+///
+/// This is the check for each [`Point`] on the hot path (see `IsTransform::transform`).
+#[track_caller]
+pub(crate) fn debug_assert_src<Id, Repr>(
+    src: CoordinateSystem<Id, Repr>,
+    found: CoordinateSystem<Id, Repr>,
+) where
+    Id: IsCoordinateSystemId,
+    Repr: Debug + Copy + Serialize,
+{
+    if cfg!(debug_assertions) {
+        assert_src(src, found);
+    }
+}
+
+/// Error returned by [`IsTransform::try_transform`] when a [`Point`] is not in the `src` [`CoordinateSystem`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoordinateSystemMismatch {
+    pub expected: DynCoordinateSystem,
+    pub found: DynCoordinateSystem,
+}
+
+impl Display for CoordinateSystemMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Transform source coordinate system {:?} does not match Point coordinate system {:?} ({}).",
+            self.expected,
+            self.found,
+            self.expected.diff(&self.found),
+        )
+    }
+}
+
+impl std::error::Error for CoordinateSystemMismatch {}
+
+/// Side on which an increment `exp(δ)` is applied to an [`SE3Transform`] `T`, with `δ = [ω; v]` (rotation first).
+///
+/// Optimization libraries differ: e.g. Ceres-style local parameterizations use `Right`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Perturbation {
+    /// `exp(δ) * T`, i.e. `δ` is expressed in the `dst` [`CoordinateSystem`].
+    Left,
+    /// `T * exp(δ)`, i.e. `δ` is expressed in the `src` [`CoordinateSystem`].
+    Right,
 }
 
 /// Represents a Transform between two SE3 [`CoordinateSystem`]s.
@@ -49,6 +167,9 @@ where
     dst: CoordinateSystem<DstId, Isometry3<T>>,
     src: CoordinateSystem<SrcId, Isometry3<T>>,
     transform: Isometry3<T>,
+    /// Whether `transform` is exactly identity, computed once in [`SE3Transform::new`].
+    #[serde(skip)]
+    identity: bool,
 }
 
 impl<DstId, SrcId, T> IsTransform<DstId, Isometry3<T>, SrcId, Isometry3<T>>
@@ -65,8 +186,50 @@ where
         self.src
     }
     fn transform_inner(&self, point: Point<SrcId, Isometry3<T>>) -> Point<DstId, Isometry3<T>> {
+        if self.is_identity() {
+            return Point::new(self.dst(), point.coordinates());
+        }
         Point::new(self.dst(), self.transform * point.coordinates())
     }
+    fn transform_ref(&self, point: &Point<SrcId, Isometry3<T>>) -> Point<DstId, Isometry3<T>> {
+        debug_assert_src(self.src(), point.coordinate_system());
+        if self.is_identity() {
+            return Point::new(self.dst(), *point.coordinates_ref());
+        }
+        Point::new(self.dst(), self.transform * point.coordinates_ref())
+    }
+}
+
+impl<Id, T> SE3Transform<Id, Id, T>
+where
+    Id: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+    Isometry3<T>: IsReprOf<Id::Kind>,
+{
+    /// Motion of a single frame `Id` between two times, i.e. the Transform `Id@t1 <- Id@t0`.
+    ///
+    /// Applying it moves a [`Point`] from the [`CoordinateSystem`] at `t0` to the one at `t1`.
+    pub fn motion(t0: u64, t1: u64, isometry: Isometry3<T>) -> Self {
+        SE3Transform::new(
+            CoordinateSystem::at_time(t1),
+            CoordinateSystem::at_time(t0),
+            isometry,
+        )
+    }
+}
+
+/// `transform * point` is shorthand for `transform.transform(point)`, including its (debug-only) run-time check.
+impl<DstId, SrcId, T> Mul<Point<SrcId, Isometry3<T>>> for SE3Transform<DstId, SrcId, T>
+where
+    DstId: IsCoordinateSystemId,
+    SrcId: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    type Output = Point<DstId, Isometry3<T>>;
+
+    fn mul(self, point: Point<SrcId, Isometry3<T>>) -> Self::Output {
+        self.transform(point)
+    }
 }
 
 impl<DstId, SrcId, T> SE3Transform<DstId, SrcId, T>
@@ -81,13 +244,42 @@ where
         transform: Isometry3<T>,
     ) -> Self {
         // TODO: figure out some way to prevent setting identity transform
+        assert_same_handedness::<DstId, SrcId>();
         Self {
             dst,
             src,
             transform,
+            identity: transform.translation.vector == Vector3::zeros()
+                && transform.rotation.imag() == Vector3::zeros(),
         }
     }
 
+    pub fn isometry(&self) -> Isometry3<T> {
+        self.transform
+    }
+
+    /// Apply the Transform to a bare position `p` written in `src`, i.e. `R * p + t`.
+    ///
+    /// Unlike `transform`, there is no [`CoordinateSystem`] to check: the caller is responsible
+    /// for knowing that `p` is written in `src`.
+    pub fn transform_point3(&self, p: Vector3<T>) -> Vector3<T> {
+        self.transform.rotation * p + self.transform.translation.vector
+    }
+
+    /// Rotational part of the Transform, i.e. the orientation of `src` relative to `dst`, ignoring translation.
+    pub fn rotation(&self) -> UnitQuaternion<T> {
+        self.transform.rotation
+    }
+
+    /// Whether the stored isometry is exactly identity, i.e. its translation and the imaginary part
+    /// of its rotation quaternion are exactly zero. This is computed once, when the Transform is built.
+    ///
+    /// Identity Transforms skip the multiplication in `transform`, and only re-tag the [`CoordinateSystem`].
+    /// Since the check is exact, no real motion is ever dropped, however small.
+    pub fn is_identity(&self) -> bool {
+        self.identity
+    }
+
     /// Invert a Transform between two SE3 [`CoordinateSystem`]s.
     pub fn invert(&self) -> SE3Transform<SrcId, DstId, T> {
         SE3Transform::new(self.src, self.dst, self.transform.inverse())
@@ -103,12 +295,206 @@ where
     {
         assert!(
             self.src() == rhs.dst(),
-            "Source coordinate system of `self` {:?} does not match Destination coordinate system of `rhs` {:?}.",
+            "Source coordinate system of `self` {:?} does not match Destination coordinate system of `rhs` {:?} ({}).",
             self.src(),
             rhs.dst(),
+            DynCoordinateSystem::from(self.src()).diff(&rhs.dst().into()),
         );
         SE3Transform::new(self.dst, rhs.src(), self.transform * rhs.transform)
     }
+
+    /// Apply the increment `exp(δ)` on the given side, with `δ = [ω; v]` (rotation first).
+    pub fn oplus(&self, delta: Vector6<T>, perturbation: Perturbation) -> Self {
+        let increment = se3::exp(delta);
+        let transform = match perturbation {
+            Perturbation::Left => increment * self.transform,
+            Perturbation::Right => self.transform * increment,
+        };
+        Self::new(self.dst, self.src, transform)
+    }
+
+    /// Adjoint of the Transform, acting on `δ = [ω; v]`, so that `T * exp(δ) == exp(Ad(T) δ) * T`.
+    ///
+    /// Jacobians with respect to a [`Perturbation::Left`] convert to [`Perturbation::Right`] as `J * Ad(T)`.
+    pub fn adjoint(&self) -> Matrix6<T> {
+        let rotation = self.transform.rotation.to_rotation_matrix().into_inner();
+        let mut adjoint = Matrix6::zeros();
+        adjoint.fixed_slice_mut::<3, 3>(0, 0).copy_from(&rotation);
+        adjoint
+            .fixed_slice_mut::<3, 3>(3, 0)
+            .copy_from(&(self.transform.translation.vector.cross_matrix() * rotation));
+        adjoint.fixed_slice_mut::<3, 3>(3, 3).copy_from(&rotation);
+        adjoint
+    }
+
+    /// Interpolate between two [`SE3Transform`]s over the same ids, e.g. extrinsics at two different times.
+    ///
+    /// The isometry follows the SE3 geodesic (screw motion) `self * exp(alpha * log(self⁻¹ * other))`,
+    /// so rotation and translation are interpolated together. The times of the `dst` and `src`
+    /// [`CoordinateSystem`]s are interpolated with integer arithmetic, rounding to the nearest time.
+    /// `alpha` must be in `[0, 1]`; `0` gives `self` and `1` gives `other`.
+    pub fn lerp_to(&self, other: &Self, alpha: T) -> Self {
+        self.interpolated(
+            other,
+            alpha,
+            self.transform * se3::exp(se3::log(self.transform.inverse() * other.transform) * alpha),
+        )
+    }
+
+    /// Interpolate like `lerp_to`, but with rotation and translation decoupled: the rotation is slerped,
+    /// and the translation is interpolated linearly, i.e. the origin of `src` moves along a straight line.
+    ///
+    /// This matches `lerp_to` for pure rotations, but not once the Transforms also differ by a translation.
+    pub fn lerp_decoupled(&self, other: &Self, alpha: T) -> Self {
+        let (start, end) = (self.transform, other.transform);
+        self.interpolated(
+            other,
+            alpha,
+            Isometry3::from_parts(
+                Translation3::from(
+                    start
+                        .translation
+                        .vector
+                        .lerp(&end.translation.vector, alpha),
+                ),
+                start.rotation.slerp(&end.rotation, alpha),
+            ),
+        )
+    }
+
+    /// The Transform with the given `isometry` at the times interpolated between `self` and `other`.
+    fn interpolated(&self, other: &Self, alpha: T, isometry: Isometry3<T>) -> Self {
+        assert!(
+            alpha >= T::zero() && alpha <= T::one(),
+            "Interpolation factor must be in [0, 1], got {}.",
+            alpha,
+        );
+        SE3Transform::new(
+            self.dst
+                .with_time(lerp_time(self.dst.time(), other.dst.time(), alpha)),
+            self.src
+                .with_time(lerp_time(self.src.time(), other.src.time(), alpha)),
+            isometry,
+        )
+    }
+}
+
+/// Interpolate between two times without converting them to floating-point, so that times above
+/// `2^53` keep full precision. `alpha` (in `[0, 1]`) is quantized to 32 fractional bits.
+fn lerp_time<T: Copy + RealField>(a: u64, b: u64, alpha: T) -> u64 {
+    const ONE: u128 = 1 << 32;
+    let alpha: f64 = nalgebra::try_convert(alpha).expect("`alpha` must be representable as f64.");
+    let alpha = (alpha * ONE as f64).round() as u128;
+    let (low, high, alpha) = if a <= b {
+        (a, b, alpha)
+    } else {
+        (b, a, ONE - alpha)
+    };
+    low + (((high - low) as u128 * alpha + ONE / 2) >> 32) as u64
+}
+
+/// Rigid-body Transforms cannot change handedness, so check that both ids agree.
+pub(crate) fn assert_same_handedness<DstId, SrcId>()
+where
+    DstId: IsCoordinateSystemId,
+    SrcId: IsCoordinateSystemId,
+{
+    assert!(
+        DstId::HANDEDNESS == SrcId::HANDEDNESS,
+        "Cannot build a rigid-body Transform from {} ({:?}-handed) to {} ({:?}-handed); use a HandednessFlip.",
+        SrcId::name(),
+        SrcId::HANDEDNESS,
+        DstId::name(),
+        DstId::HANDEDNESS,
+    );
+}
+
+/// Represents a change of handedness between two SE3 [`CoordinateSystem`]s, by mirroring one axis.
+///
+/// Poses are conjugated by the mirror `M`: the position `t` becomes `M * t` and the rotation `R`
+/// becomes `M * R * M`, which is again a proper rotation.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct HandednessFlip<DstId, SrcId, T>
+where
+    DstId: IsCoordinateSystemId,
+    SrcId: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    dst: CoordinateSystem<DstId, Isometry3<T>>,
+    src: CoordinateSystem<SrcId, Isometry3<T>>,
+    axis: usize,
+}
+
+impl<DstId, SrcId, T> IsTransform<DstId, Isometry3<T>, SrcId, Isometry3<T>>
+    for HandednessFlip<DstId, SrcId, T>
+where
+    DstId: IsCoordinateSystemId,
+    SrcId: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    fn dst(&self) -> CoordinateSystem<DstId, Isometry3<T>> {
+        self.dst
+    }
+    fn src(&self) -> CoordinateSystem<SrcId, Isometry3<T>> {
+        self.src
+    }
+    fn transform_inner(&self, point: Point<SrcId, Isometry3<T>>) -> Point<DstId, Isometry3<T>> {
+        let mut mirror = Matrix3::identity();
+        mirror[(self.axis, self.axis)] = -T::one();
+        let coordinates = point.coordinates();
+        let rotation = mirror * coordinates.rotation.to_rotation_matrix().matrix() * mirror;
+        Point::new(
+            self.dst(),
+            Isometry3::from_parts(
+                Translation3::from(mirror * coordinates.translation.vector),
+                UnitQuaternion::from_rotation_matrix(&Rotation3::from_matrix_unchecked(rotation)),
+            ),
+        )
+    }
+}
+
+impl<DstId, SrcId, T> HandednessFlip<DstId, SrcId, T>
+where
+    DstId: IsCoordinateSystemId,
+    SrcId: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    /// Flip handedness by mirroring `axis` (0, 1 or 2 for x, y or z).
+    pub fn new(
+        dst: CoordinateSystem<DstId, Isometry3<T>>,
+        src: CoordinateSystem<SrcId, Isometry3<T>>,
+        axis: usize,
+    ) -> Self {
+        assert!(axis < 3, "Axis must be 0, 1 or 2, got {}.", axis);
+        assert!(
+            DstId::HANDEDNESS != SrcId::HANDEDNESS,
+            "{} and {} have the same handedness {:?}; use an SE3Transform.",
+            DstId::name(),
+            SrcId::name(),
+            DstId::HANDEDNESS,
+        );
+        Self { dst, src, axis }
+    }
+
+    /// Invert a [`HandednessFlip`]. Mirroring is its own inverse.
+    pub fn invert(&self) -> HandednessFlip<SrcId, DstId, T> {
+        HandednessFlip::new(self.src, self.dst, self.axis)
+    }
+}
+
+/// Compose a chain of [`SE3Transform`]s within a single `Id`, e.g. successive motions of one Camera.
+///
+/// `transforms[i]` is composed with `transforms[i + 1]`, so the result goes from the `src` of the last
+/// Transform to the `dst` of the first. Returns `None` for an empty chain.
+///
+/// Panics, as `compose_with` does, if two neighbouring Transforms are not adjacent.
+pub fn compose_all<Id, T>(transforms: &[SE3Transform<Id, Id, T>]) -> Option<SE3Transform<Id, Id, T>>
+where
+    Id: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    let (first, rest) = transforms.split_first()?;
+    Some(rest.iter().fold(*first, |acc, rhs| acc.compose_with(*rhs)))
 }
 
 /// Represents a Transform from an SE3 [`CoordinateSystem`] to an Image-Plane [`CoordinateSystem`].
@@ -138,15 +524,10 @@ where
         self.src
     }
     fn transform_inner(&self, point: Point<SrcId, Isometry3<T>>) -> Point<DstId, Vector2<T>> {
-        let unnormalized_coords = self.k * point.coordinates().translation.vector;
-        if unnormalized_coords[2] <= T::zero() {
-            log::warn!("Projection had z-coordinate <= 0. Thus the Point may be phyically behind the Camera.");
-        }
-        let normalized_coords = Vector2::new(
-            unnormalized_coords[0] / unnormalized_coords[2],
-            unnormalized_coords[1] / unnormalized_coords[2],
-        );
-        Point::new(self.dst(), normalized_coords)
+        Point::new(
+            self.dst(),
+            self.project_vector(point.coordinates().translation.vector),
+        )
     }
 }
 
@@ -170,4 +551,698 @@ where
         );
         Self { dst, src, k }
     }
+
+    /// Build a [`ProjectiveTransform`] from a physical Camera spec rather than pixel intrinsics.
+    ///
+    /// The focal length and sensor size are in the same unit (e.g. mm). Pixels may be non-square,
+    /// so `fx` and `fy` are computed separately from the sensor width and height. The principal
+    /// point is assumed to be at the center of the image, `(width_px / 2, height_px / 2)`.
+    pub fn from_physical(
+        dst: CoordinateSystem<DstId, Vector2<T>>,
+        src: CoordinateSystem<SrcId, Isometry3<T>>,
+        focal_length: T,
+        sensor_width: T,
+        sensor_height: T,
+        width_px: usize,
+        height_px: usize,
+    ) -> Self {
+        assert!(
+            focal_length > T::zero() && sensor_width > T::zero() && sensor_height > T::zero(),
+            "Focal length and sensor size must be positive, got {} and [{}, {}].",
+            focal_length,
+            sensor_width,
+            sensor_height,
+        );
+        assert!(
+            width_px > 0 && height_px > 0,
+            "Image size must be positive, got [{}, {}].",
+            width_px,
+            height_px,
+        );
+        let width_px: T = nalgebra::convert(width_px as f64);
+        let height_px: T = nalgebra::convert(height_px as f64);
+        let two: T = nalgebra::convert(2.);
+        #[rustfmt::skip]
+        let k = Matrix3::new(
+            focal_length * width_px / sensor_width, T::zero(), width_px / two,
+            T::zero(), focal_length * height_px / sensor_height, height_px / two,
+            T::zero(), T::zero(), T::one(),
+        );
+        Self::new(dst, src, k)
+    }
+
+    /// Build a [`ProjectiveTransform`] from the pinhole intrinsics parameters, with zero skew,
+    /// as for most modern Cameras. Use `with_skew` for the full 5-parameter `K`.
+    pub fn from_intrinsics(
+        dst: CoordinateSystem<DstId, Vector2<T>>,
+        src: CoordinateSystem<SrcId, Isometry3<T>>,
+        fx: T,
+        fy: T,
+        cx: T,
+        cy: T,
+    ) -> Self {
+        #[rustfmt::skip]
+        let k = Matrix3::new(
+            fx, T::zero(), cx,
+            T::zero(), fy, cy,
+            T::zero(), T::zero(), T::one(),
+        );
+        Self::new(dst, src, k)
+    }
+
+    /// Set the skew between the image axes, `K[(0, 1)]`, e.g. `from_intrinsics(...).with_skew(skew)`.
+    pub fn with_skew(self, skew: T) -> Self {
+        let mut k = self.k;
+        k[(0, 1)] = skew;
+        Self::new(self.dst, self.src, k)
+    }
+
+    pub fn k(&self) -> Matrix3<T> {
+        self.k
+    }
+
+    /// Skew between the image axes, i.e. `K[(0, 1)]`.
+    pub fn skew(&self) -> T {
+        self.k[(0, 1)]
+    }
+
+    /// Project a [`Point`] (with the same check as `transform`), returning `None` if it is behind the Camera.
+    pub fn project_if_visible(
+        &self,
+        point: Point<SrcId, Isometry3<T>>,
+    ) -> Option<Point<DstId, Vector2<T>>> {
+        debug_assert_src(self.src(), point.coordinate_system());
+        let (pixel, visible) =
+            self.project_vector_with_visibility(point.coordinates().translation.vector);
+        visible.then(|| Point::new(self.dst(), pixel))
+    }
+
+    /// Project many [`Point`]s at once with [`ProjectiveTransform::project_if_visible`], returning `None`
+    /// for those which are behind the Camera or outside of a `width` x `height` image.
+    ///
+    /// As in [`crate::bilinear_sample`], pixel centers are at integer coordinates, so a pixel is in the image
+    /// if `0 <= x <= width - 1` and `0 <= y <= height - 1`. Each [`Point`] gets the same (debug-only) check
+    /// as `transform`.
+    pub fn project_batch(
+        &self,
+        points: &[Point<SrcId, Isometry3<T>>],
+        width: usize,
+        height: usize,
+    ) -> Vec<Option<Point<DstId, Vector2<T>>>> {
+        let max_x: T = nalgebra::convert(width as f64 - 1.);
+        let max_y: T = nalgebra::convert(height as f64 - 1.);
+        points
+            .iter()
+            .map(|point| {
+                self.project_if_visible(*point).filter(|pixel| {
+                    let (x, y) = (pixel.coordinates()[0], pixel.coordinates()[1]);
+                    x >= T::zero() && x <= max_x && y >= T::zero() && y <= max_y
+                })
+            })
+            .collect()
+    }
+
+    /// Invert the projection. Since projection loses depth, the result is an [`UnprojectiveTransform`],
+    /// which requires the depth of each pixel to recover the [`Point`].
+    pub fn invert(&self) -> UnprojectiveTransform<SrcId, DstId, T> {
+        UnprojectiveTransform::new(self.src, self.dst, self.k)
+    }
+
+    /// Project a [`InverseDepthPoint`] anchored in some other SE3 [`CoordinateSystem`].
+    ///
+    /// The Point is moved into the `src` [`CoordinateSystem`] as `R * bearing + inverse_depth * t`,
+    /// which is the Euclidean Point scaled by `inverse_depth`. Since projection is scale-invariant,
+    /// this stays finite and stable for Points at (or near) infinity.
+    pub fn project_inverse_depth<AnchorId>(
+        &self,
+        point: InverseDepthPoint<AnchorId, T>,
+        src_from_anchor: SE3Transform<SrcId, AnchorId, T>,
+    ) -> Point<DstId, Vector2<T>>
+    where
+        AnchorId: IsCoordinateSystemId,
+    {
+        // Checking that the Transforms are adjacent is a composition check, so it always runs.
+        assert_src(self.src(), src_from_anchor.dst());
+        assert_src(src_from_anchor.src(), point.anchor());
+        let isometry = src_from_anchor.transform;
+        let scaled_coords = isometry.rotation * point.bearing()
+            + isometry.translation.vector * point.inverse_depth();
+        Point::new(self.dst(), self.project_vector(scaled_coords))
+    }
+
+    /// Project a 3-D [`Line3`] into the image, as the homogeneous line `[a, b, c]` which the projection
+    /// of every Point on it satisfies: `a * x + b * y + c == 0`.
+    ///
+    /// For a Camera at the origin of `src`, this is the Plücker moment mapped through `K^-T`.
+    pub fn project_line(&self, line: Line3<SrcId, T>) -> Vector3<T> {
+        debug_assert_src(self.src(), line.coordinate_system());
+        self.k_inv_transpose() * line.moment()
+    }
+
+    /// Project a [`Point`] (with the same check as `transform`), and compute the Jacobians of the pixel
+    /// in one pass, reusing the intermediate quantities.
+    ///
+    /// Returns `(pixel, jacobian_wrt_pose, jacobian_wrt_point)`:
+    /// - `jacobian_wrt_point` is the 2x3 Jacobian with respect to the position of the Point in `src`.
+    /// - `jacobian_wrt_pose` is the 2x6 Jacobian with respect to a left perturbation `exp(δ) * src_from_world`
+    ///   of the Transform which moved the Point into `src`, with `δ = [ω; v]` (rotation first).
+    pub fn project_with_jacobian(
+        &self,
+        point: Point<SrcId, Isometry3<T>>,
+    ) -> (Point<DstId, Vector2<T>>, Matrix2x6<T>, Matrix2x3<T>) {
+        debug_assert_src(self.src(), point.coordinate_system());
+        let p = point.coordinates().translation.vector;
+        let pixel = self.project_vector(p);
+
+        // d(pixel)/d(K p) for the perspective division, then chained through K.
+        let unnormalized_coords = self.k * p;
+        let inv_z = T::one() / unnormalized_coords[2];
+        #[rustfmt::skip]
+        let d_pixel_d_unnormalized = Matrix2x3::new(
+            inv_z, T::zero(), -pixel[0] * inv_z,
+            T::zero(), inv_z, -pixel[1] * inv_z,
+        );
+        let jacobian_wrt_point = d_pixel_d_unnormalized * self.k;
+
+        // d(exp(δ) p)/dδ = [-[p]x, I].
+        let mut d_point_d_pose = Matrix3x6::zeros();
+        d_point_d_pose
+            .fixed_columns_mut::<3>(0)
+            .copy_from(&(-p.cross_matrix()));
+        d_point_d_pose
+            .fixed_columns_mut::<3>(3)
+            .copy_from(&Matrix3::identity());
+        let jacobian_wrt_pose = jacobian_wrt_point * d_point_d_pose;
+
+        (
+            Point::new(self.dst(), pixel),
+            jacobian_wrt_pose,
+            jacobian_wrt_point,
+        )
+    }
+
+    /// `K^-T` in closed form, without a general 3x3 inversion.
+    ///
+    /// Since the last row of `K` is `[0, 0, 1]`, `K^-1` is the inverse `A^-1` of its upper-left 2x2 block
+    /// (focal lengths and skew), with last column `-A^-1 * [cx, cy]`.
+    fn k_inv_transpose(&self) -> Matrix3<T> {
+        let k = &self.k;
+        let det = k[(0, 0)] * k[(1, 1)] - k[(0, 1)] * k[(1, 0)];
+        assert!(
+            det != T::zero(),
+            "Camera intrinsics matrix must be invertible."
+        );
+        let (a00, a01) = (k[(1, 1)] / det, -k[(0, 1)] / det);
+        let (a10, a11) = (-k[(1, 0)] / det, k[(0, 0)] / det);
+        let (cx, cy) = (k[(0, 2)], k[(1, 2)]);
+        #[rustfmt::skip]
+        let k_inv_transpose = Matrix3::new(
+            a00, a10, T::zero(),
+            a01, a11, T::zero(),
+            -(a00 * cx + a01 * cy), -(a10 * cx + a11 * cy), T::one(),
+        );
+        k_inv_transpose
+    }
+
+    /// Apply the intrinsics `k` to a (possibly scaled) 3-D position in the `src` [`CoordinateSystem`].
+    fn project_vector(&self, coords: Vector3<T>) -> Vector2<T> {
+        let (pixel, visible) = self.project_vector_with_visibility(coords);
+        if !visible {
+            log::warn!("Projection had z-coordinate <= 0. Thus the Point may be phyically behind the Camera.");
+        }
+        pixel
+    }
+
+    /// Apply the intrinsics `k`, and report whether the position is in front of the Camera (z-coordinate > 0).
+    ///
+    /// This is the one projection and visibility code path, shared by all of the projection methods.
+    fn project_vector_with_visibility(&self, coords: Vector3<T>) -> (Vector2<T>, bool) {
+        let unnormalized_coords = self.k * coords;
+        (
+            Vector2::new(
+                unnormalized_coords[0] / unnormalized_coords[2],
+                unnormalized_coords[1] / unnormalized_coords[2],
+            ),
+            unnormalized_coords[2] > T::zero(),
+        )
+    }
+}
+
+/// Warp a pixel with known `depth` from one Camera's image into another's, e.g. for stereo or RGBD warping.
+///
+/// The pixel is unprojected by `src` (with `depth` as its z-coordinate), moved by `relative`, and projected
+/// by `dst` with [`ProjectiveTransform::project_if_visible`]. Returns `None` if the Point ends up behind the
+/// `dst` Camera. `src` is an [`UnprojectiveTransform`] (see [`ProjectiveTransform::invert`]), so that its
+/// `K^-1` is computed once rather than for every pixel.
+pub fn reproject_depth<DstImg, DstSE3, SrcSE3, SrcImg, T>(
+    src: &UnprojectiveTransform<SrcSE3, SrcImg, T>,
+    relative: &SE3Transform<DstSE3, SrcSE3, T>,
+    dst: &ProjectiveTransform<DstImg, DstSE3, T>,
+    pixel: Vector2<T>,
+    depth: T,
+) -> Option<Point<DstImg, Vector2<T>>>
+where
+    DstImg: IsCoordinateSystemId,
+    DstSE3: IsCoordinateSystemId,
+    SrcSE3: IsCoordinateSystemId,
+    SrcImg: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    let point = src.transform_with_depth(Point::new(src.src(), pixel), depth);
+    debug_assert_src(relative.src(), point.coordinate_system());
+    dst.project_if_visible(relative.transform_inner(point))
+}
+
+/// Represents a reusable "world to pixel" pipeline: an [`SE3Transform`] into a Camera's SE3
+/// [`CoordinateSystem`], followed by that Camera's [`ProjectiveTransform`].
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ProjectionPipeline<ImageId, CameraId, WorldId, T>
+where
+    ImageId: IsCoordinateSystemId,
+    CameraId: IsCoordinateSystemId,
+    WorldId: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    camera_from_world: SE3Transform<CameraId, WorldId, T>,
+    projection: ProjectiveTransform<ImageId, CameraId, T>,
+}
+
+impl<ImageId, CameraId, WorldId, T> IsTransform<ImageId, Vector2<T>, WorldId, Isometry3<T>>
+    for ProjectionPipeline<ImageId, CameraId, WorldId, T>
+where
+    ImageId: IsCoordinateSystemId,
+    CameraId: IsCoordinateSystemId,
+    WorldId: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    fn dst(&self) -> CoordinateSystem<ImageId, Vector2<T>> {
+        self.projection.dst()
+    }
+    fn src(&self) -> CoordinateSystem<WorldId, Isometry3<T>> {
+        self.camera_from_world.src()
+    }
+    fn transform_inner(&self, point: Point<WorldId, Isometry3<T>>) -> Point<ImageId, Vector2<T>> {
+        self.projection
+            .transform_inner(self.camera_from_world.transform_inner(point))
+    }
+}
+
+impl<ImageId, CameraId, WorldId, T> ProjectionPipeline<ImageId, CameraId, WorldId, T>
+where
+    ImageId: IsCoordinateSystemId,
+    CameraId: IsCoordinateSystemId,
+    WorldId: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    pub fn new(
+        camera_from_world: SE3Transform<CameraId, WorldId, T>,
+        projection: ProjectiveTransform<ImageId, CameraId, T>,
+    ) -> Self {
+        assert!(
+            projection.src() == camera_from_world.dst(),
+            "Source coordinate system of `projection` {:?} does not match Destination coordinate system of `camera_from_world` {:?}.",
+            projection.src(),
+            camera_from_world.dst(),
+        );
+        Self {
+            camera_from_world,
+            projection,
+        }
+    }
+
+    /// Project a [`Point`] in the world [`CoordinateSystem`] into the image. Equivalent to `transform`.
+    pub fn project(&self, point: Point<WorldId, Isometry3<T>>) -> Point<ImageId, Vector2<T>> {
+        self.transform(point)
+    }
+
+    /// Project a [`Point`] in the world [`CoordinateSystem`] into the image, along with the Jacobians of the pixel.
+    ///
+    /// Returns `(pixel, jacobian_wrt_pose, jacobian_wrt_point)`, where `jacobian_wrt_pose` is with respect to
+    /// `camera_from_world.oplus(δ, perturbation)` and `jacobian_wrt_point` is with respect to the world position.
+    pub fn project_with_jacobian(
+        &self,
+        point: Point<WorldId, Isometry3<T>>,
+        perturbation: Perturbation,
+    ) -> (Point<ImageId, Vector2<T>>, Matrix2x6<T>, Matrix2x3<T>) {
+        let (pixel, jacobian_wrt_left_pose, jacobian_wrt_camera_point) = self
+            .projection
+            .project_with_jacobian(self.camera_from_world.transform(point));
+        let jacobian_wrt_pose = match perturbation {
+            Perturbation::Left => jacobian_wrt_left_pose,
+            Perturbation::Right => jacobian_wrt_left_pose * self.camera_from_world.adjoint(),
+        };
+        let jacobian_wrt_point = jacobian_wrt_camera_point
+            * self
+                .camera_from_world
+                .rotation()
+                .to_rotation_matrix()
+                .into_inner();
+        (pixel, jacobian_wrt_pose, jacobian_wrt_point)
+    }
+}
+
+/// Represents the inverse of a [`ProjectiveTransform`], from an Image-Plane [`CoordinateSystem`]
+/// back to an SE3 [`CoordinateSystem`].
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct UnprojectiveTransform<DstId, SrcId, T>
+where
+    DstId: IsCoordinateSystemId,
+    SrcId: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    dst: CoordinateSystem<DstId, Isometry3<T>>,
+    src: CoordinateSystem<SrcId, Vector2<T>>,
+    k_inv: Matrix3<T>,
+}
+
+impl<DstId, SrcId, T> UnprojectiveTransform<DstId, SrcId, T>
+where
+    DstId: IsCoordinateSystemId,
+    SrcId: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    /// Build from the camera intrinsics matrix `k` (not its inverse).
+    pub fn new(
+        dst: CoordinateSystem<DstId, Isometry3<T>>,
+        src: CoordinateSystem<SrcId, Vector2<T>>,
+        k: Matrix3<T>,
+    ) -> Self {
+        let k_inv = k
+            .try_inverse()
+            .expect("Camera intrinsics matrix must be invertible.");
+        Self { dst, src, k_inv }
+    }
+
+    pub fn dst(&self) -> CoordinateSystem<DstId, Isometry3<T>> {
+        self.dst
+    }
+
+    pub fn src(&self) -> CoordinateSystem<SrcId, Vector2<T>> {
+        self.src
+    }
+
+    pub fn k_inv(&self) -> Matrix3<T> {
+        self.k_inv
+    }
+
+    /// Unit-norm direction from the Camera center through the pixel, in the `dst` [`CoordinateSystem`].
+    ///
+    /// Uses the exact `K^-1`, so intrinsics with nonzero skew are handled.
+    pub fn bearing(&self, pixel: Point<SrcId, Vector2<T>>) -> Vector3<T> {
+        self.ray(pixel).normalize()
+    }
+
+    /// Recover the [`Point`] which projects to `pixel` and has z-coordinate `depth` in the `dst` [`CoordinateSystem`].
+    pub fn transform_with_depth(
+        &self,
+        pixel: Point<SrcId, Vector2<T>>,
+        depth: T,
+    ) -> Point<DstId, Isometry3<T>> {
+        Point::new(
+            self.dst,
+            Isometry3::from_parts(
+                Translation3::from(self.ray(pixel) * depth),
+                UnitQuaternion::identity(),
+            ),
+        )
+    }
+
+    /// Ray through the pixel, scaled to have z-coordinate 1.
+    fn ray(&self, pixel: Point<SrcId, Vector2<T>>) -> Vector3<T> {
+        debug_assert_src(self.src, pixel.coordinate_system());
+        self.k_inv * pixel.coordinates().push(T::one())
+    }
+}
+
+/// Represents a linear Transform between two N-dimensional [`CoordinateSystem`]s,
+/// mapping a [`Point`] in `R^N` to a [`Point`] in `R^M`.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct LinearTransform<DstId, SrcId, const M: usize, const N: usize, T>
+where
+    DstId: IsCoordinateSystemId,
+    SrcId: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    dst: CoordinateSystem<DstId, SVector<T, M>>,
+    src: CoordinateSystem<SrcId, SVector<T, N>>,
+    matrix: SMatrix<T, M, N>,
+}
+
+impl<DstId, SrcId, const M: usize, const N: usize, T>
+    IsTransform<DstId, SVector<T, M>, SrcId, SVector<T, N>>
+    for LinearTransform<DstId, SrcId, M, N, T>
+where
+    DstId: IsCoordinateSystemId,
+    SrcId: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    fn dst(&self) -> CoordinateSystem<DstId, SVector<T, M>> {
+        self.dst
+    }
+    fn src(&self) -> CoordinateSystem<SrcId, SVector<T, N>> {
+        self.src
+    }
+    fn transform_inner(&self, point: Point<SrcId, SVector<T, N>>) -> Point<DstId, SVector<T, M>> {
+        Point::new(self.dst(), self.matrix * point.coordinates())
+    }
+    fn transform_ref(&self, point: &Point<SrcId, SVector<T, N>>) -> Point<DstId, SVector<T, M>> {
+        debug_assert_src(self.src(), point.coordinate_system());
+        Point::new(self.dst(), self.matrix * point.coordinates_ref())
+    }
+}
+
+impl<DstId, SrcId, const M: usize, const N: usize, T> LinearTransform<DstId, SrcId, M, N, T>
+where
+    DstId: IsCoordinateSystemId,
+    SrcId: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    pub fn new(
+        dst: CoordinateSystem<DstId, SVector<T, M>>,
+        src: CoordinateSystem<SrcId, SVector<T, N>>,
+        matrix: SMatrix<T, M, N>,
+    ) -> Self {
+        Self { dst, src, matrix }
+    }
+
+    pub fn matrix(&self) -> SMatrix<T, M, N> {
+        self.matrix
+    }
+
+    /// Compose two [`LinearTransform`]s, i.e. take the product of their matrices.
+    pub fn compose_with<RhsSrcId, const P: usize>(
+        &self,
+        rhs: LinearTransform<SrcId, RhsSrcId, N, P, T>,
+    ) -> LinearTransform<DstId, RhsSrcId, M, P, T>
+    where
+        RhsSrcId: IsCoordinateSystemId,
+    {
+        assert!(
+            self.src() == rhs.dst(),
+            "Source coordinate system of `self` {:?} does not match Destination coordinate system of `rhs` {:?} ({}).",
+            self.src(),
+            rhs.dst(),
+            DynCoordinateSystem::from(self.src()).diff(&rhs.dst().into()),
+        );
+        LinearTransform::new(self.dst, rhs.src(), self.matrix * rhs.matrix)
+    }
+}
+
+/// Represents a per-axis (anisotropic) scaling between two 3-D [`CoordinateSystem`]s,
+/// e.g. from metric coordinates to voxel-grid indices.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ScaleTransform<DstId, SrcId, T>
+where
+    DstId: IsCoordinateSystemId,
+    SrcId: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    dst: CoordinateSystem<DstId, Vector3<T>>,
+    src: CoordinateSystem<SrcId, Vector3<T>>,
+    scale: Vector3<T>,
+}
+
+impl<DstId, SrcId, T> IsTransform<DstId, Vector3<T>, SrcId, Vector3<T>>
+    for ScaleTransform<DstId, SrcId, T>
+where
+    DstId: IsCoordinateSystemId,
+    SrcId: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    fn dst(&self) -> CoordinateSystem<DstId, Vector3<T>> {
+        self.dst
+    }
+    fn src(&self) -> CoordinateSystem<SrcId, Vector3<T>> {
+        self.src
+    }
+    fn transform_inner(&self, point: Point<SrcId, Vector3<T>>) -> Point<DstId, Vector3<T>> {
+        Point::new(self.dst(), point.coordinates().component_mul(&self.scale))
+    }
+}
+
+impl<DstId, SrcId, T> ScaleTransform<DstId, SrcId, T>
+where
+    DstId: IsCoordinateSystemId,
+    SrcId: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    pub fn new(
+        dst: CoordinateSystem<DstId, Vector3<T>>,
+        src: CoordinateSystem<SrcId, Vector3<T>>,
+        scale: Vector3<T>,
+    ) -> Self {
+        Self { dst, src, scale }
+    }
+
+    pub fn scale(&self) -> Vector3<T> {
+        self.scale
+    }
+
+    /// Invert a [`ScaleTransform`]. Panics if any of the scale factors is zero.
+    pub fn invert(&self) -> ScaleTransform<SrcId, DstId, T> {
+        assert!(
+            self.scale.iter().all(|s| *s != T::zero()),
+            "Cannot invert a ScaleTransform with a zero scale factor, got {:?}.",
+            self.scale,
+        );
+        ScaleTransform::new(self.src, self.dst, self.scale.map(|s| T::one() / s))
+    }
+
+    /// Compose two [`ScaleTransform`]s.
+    pub fn compose_with<RhsSrcId>(
+        &self,
+        rhs: ScaleTransform<SrcId, RhsSrcId, T>,
+    ) -> ScaleTransform<DstId, RhsSrcId, T>
+    where
+        RhsSrcId: IsCoordinateSystemId,
+    {
+        assert!(
+            self.src() == rhs.dst(),
+            "Source coordinate system of `self` {:?} does not match Destination coordinate system of `rhs` {:?} ({}).",
+            self.src(),
+            rhs.dst(),
+            DynCoordinateSystem::from(self.src()).diff(&rhs.dst().into()),
+        );
+        ScaleTransform::new(self.dst, rhs.src(), self.scale.component_mul(&rhs.scale))
+    }
+}
+
+/// Represents a projective Transform (homography) between two Image-Plane [`CoordinateSystem`]s.
+///
+/// Pixels are lifted to homogeneous coordinates `[x, y, 1]`, multiplied by `h`, and normalized
+/// by the resulting last coordinate.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct HomographyTransform<DstId, SrcId, T>
+where
+    DstId: IsCoordinateSystemId,
+    SrcId: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    dst: CoordinateSystem<DstId, Vector2<T>>,
+    src: CoordinateSystem<SrcId, Vector2<T>>,
+    h: Matrix3<T>,
+}
+
+impl<DstId, SrcId, T> IsTransform<DstId, Vector2<T>, SrcId, Vector2<T>>
+    for HomographyTransform<DstId, SrcId, T>
+where
+    DstId: IsCoordinateSystemId,
+    SrcId: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    fn dst(&self) -> CoordinateSystem<DstId, Vector2<T>> {
+        self.dst
+    }
+    fn src(&self) -> CoordinateSystem<SrcId, Vector2<T>> {
+        self.src
+    }
+    fn transform_inner(&self, point: Point<SrcId, Vector2<T>>) -> Point<DstId, Vector2<T>> {
+        let homogeneous_coords = self.h * point.coordinates().push(T::one());
+        if homogeneous_coords[2] == T::zero() {
+            log::warn!("Homography mapped a Point to infinity; use `transform_homogeneous` to represent it.");
+        }
+        Point::new(
+            self.dst(),
+            Vector2::new(
+                homogeneous_coords[0] / homogeneous_coords[2],
+                homogeneous_coords[1] / homogeneous_coords[2],
+            ),
+        )
+    }
+}
+
+impl<DstId, SrcId, T> HomographyTransform<DstId, SrcId, T>
+where
+    DstId: IsCoordinateSystemId,
+    SrcId: IsCoordinateSystemId,
+    T: Copy + RealField + Serialize,
+{
+    pub fn new(
+        dst: CoordinateSystem<DstId, Vector2<T>>,
+        src: CoordinateSystem<SrcId, Vector2<T>>,
+        h: Matrix3<T>,
+    ) -> Self {
+        Self { dst, src, h }
+    }
+
+    pub fn h(&self) -> Matrix3<T> {
+        self.h
+    }
+
+    /// Apply the homography to a [`HomogeneousPixel`] (RP2 to RP2), with the same check as `transform`.
+    ///
+    /// Unlike `transform`, this does not normalize by the last coordinate `w`, so Points which are mapped to
+    /// infinity (`w == 0`) are represented exactly; see [`HomogeneousPixel::to_pixel`].
+    pub fn transform_homogeneous(
+        &self,
+        point: Point<SrcId, HomogeneousPixel<T>>,
+    ) -> Point<DstId, HomogeneousPixel<T>>
+    where
+        HomogeneousPixel<T>: IsReprOf<DstId::Kind>,
+    {
+        debug_assert!(
+            DynCoordinateSystem::from(self.src) == point.coordinate_system().into(),
+            "Transform source coordinate system {:?} does not match Point coordinate system {:?} ({}).",
+            self.src,
+            point.coordinate_system(),
+            DynCoordinateSystem::from(self.src).diff(&point.coordinate_system().into()),
+        );
+        let coords = self.h * point.coordinates().coords();
+        assert!(
+            coords != Vector3::zeros(),
+            "Homography mapped {:?} to [0, 0, 0], so `h` must be singular.",
+            point.coordinates(),
+        );
+        Point::new(
+            CoordinateSystem::at_time(self.dst.time()),
+            HomogeneousPixel::new(coords),
+        )
+    }
+
+    /// Invert a homography. Panics if `h` is singular.
+    pub fn invert(&self) -> HomographyTransform<SrcId, DstId, T> {
+        let h_inv = self
+            .h
+            .try_inverse()
+            .expect("Homography matrix must be invertible.");
+        HomographyTransform::new(self.src, self.dst, h_inv)
+    }
+
+    /// Compose two [`HomographyTransform`]s.
+    pub fn compose_with<RhsSrcId>(
+        &self,
+        rhs: HomographyTransform<SrcId, RhsSrcId, T>,
+    ) -> HomographyTransform<DstId, RhsSrcId, T>
+    where
+        RhsSrcId: IsCoordinateSystemId,
+    {
+        assert!(
+            self.src() == rhs.dst(),
+            "Source coordinate system of `self` {:?} does not match Destination coordinate system of `rhs` {:?} ({}).",
+            self.src(),
+            rhs.dst(),
+            DynCoordinateSystem::from(self.src()).diff(&rhs.dst().into()),
+        );
+        HomographyTransform::new(self.dst, rhs.src(), self.h * rhs.h)
+    }
 }